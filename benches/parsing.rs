@@ -24,5 +24,39 @@ fn parse_benchmark(c: &mut Criterion) {
     }
 }
 
+#[cfg(feature = "rayon")]
+fn parallel_benchmark(c: &mut Criterion) {
+    use spanned_json_parser::parse_parallel;
+
+    let elements: Vec<String> = (0..1_000_000)
+        .map(|i| {
+            format!(
+                r#"{{"id": {}, "name": "item {}", "values": [1.5, -2]}}"#,
+                i, i
+            )
+        })
+        .collect();
+    let json = format!("[{}]", elements.join(",\n"));
+
+    let mut group = c.benchmark_group("Parallel");
+
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(json.len() as u64));
+
+    group.bench_with_input("sequential", &json, |b, data| {
+        b.iter(|| {
+            let _ = parse(black_box(data)).unwrap();
+        })
+    });
+    group.bench_with_input("parallel", &json, |b, data| {
+        b.iter(|| {
+            let _ = parse_parallel(black_box(data)).unwrap();
+        })
+    });
+}
+
+#[cfg(feature = "rayon")]
+criterion_group!(benches, parse_benchmark, parallel_benchmark);
+#[cfg(not(feature = "rayon"))]
 criterion_group!(benches, parse_benchmark);
 criterion_main!(benches);
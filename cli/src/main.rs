@@ -1,14 +1,21 @@
+use spanned_json_parser::{diagnostic::codeframe, TextRange};
 use std::{env, fs, process};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    let path = args.get(1).ok_or("Please provide a path").unwrap();
+    let path = match args.get(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: {} <path> [--pretty]", args[0]);
+            process::exit(2)
+        }
+    };
 
     let json = match fs::read_to_string(path) {
         Ok(str) => str,
         Err(e) => {
-            println!("Failed to read string: {}", e);
+            eprintln!("Failed to read string: {}", e);
             process::exit(1)
         }
     };
@@ -16,7 +23,22 @@ fn main() {
     let parsed = spanned_json_parser::parse(&json);
 
     match parsed {
-        Ok(_) => process::exit(0),
-        Err(_) => process::exit(1),
+        Ok(value) => {
+            if args.iter().any(|arg| arg == "--pretty") {
+                println!("{}", spanned_json_parser::to_string_pretty(&value, 2));
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e.kind);
+            eprintln!(" --> {}:{}:{}", path, e.start.line, e.start.col);
+
+            let span = TextRange {
+                start: e.start.clone(),
+                end: e.end.clone(),
+            };
+
+            eprint!("{}", codeframe(&json, &span, 1));
+            process::exit(1)
+        }
     }
 }
@@ -0,0 +1,54 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use spanned_json_parser::{parse, value::Number, SpannedValue, Value};
+
+/// Compares the values without their spans, as the serialized source isn't formatted the same
+/// way. `-0` is parsed as `NegInt(0)` but serialized as `0`, so the numbers are compared by value
+fn same_value(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(Number::PosInt(0)), Value::Number(Number::NegInt(0)))
+        | (Value::Number(Number::NegInt(0)), Value::Number(Number::PosInt(0))) => true,
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_value(&a.value, &b.value))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, a)| b.get(k).is_some_and(|b| same_value(&a.value, &b.value)))
+        }
+        (a, b) => a == b,
+    }
+}
+
+/// A float too big for a `f64` (ex: `1e400`) is parsed as infinity, which serde_json writes as
+/// `null`
+fn has_non_finite_float(value: &SpannedValue) -> bool {
+    std::iter::once(value)
+        .chain(value.iter_descendants())
+        .any(|v| matches!(v.value, Value::Number(Number::Float(num)) if !num.is_finite()))
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let Ok(parsed) = parse(source) else {
+        return;
+    };
+
+    if has_non_finite_float(&parsed) {
+        return;
+    }
+
+    let serialized = serde_json::to_string(&parsed).unwrap();
+    let reparsed = parse(&serialized).unwrap();
+
+    assert!(
+        same_value(&parsed.value, &reparsed.value),
+        "{} was serialized as {}",
+        source,
+        serialized
+    );
+});
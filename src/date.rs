@@ -0,0 +1,67 @@
+/// Parses `n` digits at the start of `s` and checks that they are in `range`
+fn number(s: &str, n: usize, range: std::ops::RangeInclusive<u32>) -> Option<&str> {
+    let digits = s.get(..n)?;
+
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    range.contains(&digits.parse().ok()?).then(|| &s[n..])
+}
+
+fn date(s: &str) -> Option<&str> {
+    let s = number(s, 4, 0..=9999)?;
+    let s = number(s.strip_prefix('-')?, 2, 1..=12)?;
+
+    number(s.strip_prefix('-')?, 2, 1..=31)
+}
+
+fn time(s: &str) -> Option<&str> {
+    let s = number(s, 2, 0..=23)?;
+    let mut s = number(s.strip_prefix(':')?, 2, 0..=59)?;
+
+    if let Some(rest) = s.strip_prefix(':') {
+        // 60 is allowed for leap seconds
+        s = number(rest, 2, 0..=60)?;
+
+        if let Some(rest) = s.strip_prefix('.') {
+            s = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+
+            if s.len() == rest.len() {
+                return None;
+            }
+        }
+    }
+
+    Some(s)
+}
+
+fn offset(s: &str) -> Option<&str> {
+    match s.strip_prefix('Z') {
+        Some(s) => Some(s),
+        None => {
+            let s = s.strip_prefix(['+', '-'])?;
+            let s = number(s, 2, 0..=23)?;
+
+            number(s.strip_prefix(':')?, 2, 0..=59)
+        }
+    }
+}
+
+/// Checks that `s` is an ISO-8601 date (`2023-01-01`), or date time with an optional
+/// offset (`2023-01-01T00:00:00Z`, `2023-01-01T12:30+02:00`)
+pub(crate) fn is_iso8601(s: &str) -> bool {
+    let rest = match date(s) {
+        Some(rest) => rest,
+        None => return false,
+    };
+
+    if rest.is_empty() {
+        return true;
+    }
+
+    match rest.strip_prefix('T').and_then(time) {
+        Some(rest) => rest.is_empty() || offset(rest) == Some(""),
+        None => false,
+    }
+}
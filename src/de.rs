@@ -0,0 +1,356 @@
+use crate::error::{Error, Kind};
+use crate::value::{Map, Number, Position, SpannedValue, Value};
+use serde::de::{
+    self, value::BorrowedStrDeserializer, Deserialize, DeserializeOwned, DeserializeSeed, Visitor,
+};
+use std::fmt::Display;
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        let position = Position::default();
+
+        Self::new(
+            position.clone(),
+            position,
+            Kind::Deserialize(msg.to_string()),
+        )
+    }
+}
+
+impl SpannedValue {
+    /// Locates an error raised while deserializing the value. The error keeps the span of the
+    /// deepest value, where it has been raised
+    fn locate(&self, mut error: Error) -> Error {
+        if error.start.line == 0 {
+            error.start = self.start.clone();
+            error.end = self.end.clone();
+        }
+
+        error
+    }
+}
+
+/// Deserializes a [SpannedValue] into any type implementing
+/// [Deserialize](https://docs.rs/serde/latest/serde/de/trait.Deserialize.html), without
+/// going through `serde_json`. The error is located at the value that couldn't be deserialized
+/// ```ignore
+/// use serde::Deserialize;
+/// use spanned_json_parser::{from_spanned_value, parse};
+///
+/// #[derive(Deserialize)]
+/// struct Test {
+///     pub hello: String,
+/// }
+///
+/// fn main() {
+///     let parsed = parse(r#"{"hello": "world"}"#).unwrap();
+///
+///     let test: Test = from_spanned_value(parsed).unwrap();
+///
+///     println!("Test hello: {}", test.hello);
+/// }
+/// ```
+pub fn from_spanned_value<T: DeserializeOwned>(value: SpannedValue) -> Result<T, Error> {
+    T::deserialize(&value)
+}
+
+impl<'de> de::Deserializer<'de> for &'de SpannedValue {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match &self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(bool) => visitor.visit_bool(*bool),
+            Value::Number(Number::PosInt(num)) => visitor.visit_u64(*num),
+            Value::Number(Number::NegInt(num)) => visitor.visit_i64(*num),
+            Value::Number(Number::Float(num) | Number::RawFloat(num, _)) => visitor.visit_f64(*num),
+            Value::Number(Number::Raw(text)) => match text.parse::<u128>() {
+                Ok(num) => visitor.visit_u128(num),
+                Err(_) => match text.parse::<i128>() {
+                    Ok(num) => visitor.visit_i128(num),
+                    Err(_) => visitor.visit_f64(text.parse()?),
+                },
+            },
+            Value::String(str) => visitor.visit_borrowed_str(str),
+            Value::Array(array) => visitor.visit_seq(SeqAccess { iter: array.iter() }),
+            Value::Object(_) => visitor.visit_map(MapAccess {
+                iter: self.value.object_pairs_sorted().into_iter(),
+                value: None,
+            }),
+        }
+        .map_err(|e| self.locate(e))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        // A unit variant is a string, the other variants are an object with a single key
+        let (variant, value) = match &self.value {
+            Value::String(variant) => (variant.as_str(), None),
+            Value::Object(obj) if obj.len() == 1 => obj
+                .iter()
+                .next()
+                .map(|(variant, value)| (variant.as_str(), Some(value)))
+                .unwrap_or_default(),
+            _ => {
+                return Err(self.locate(de::Error::invalid_type(
+                    de::Unexpected::Other(&format!("{:?}", self.value.value_type())),
+                    &"a string or an object with a single key",
+                )))
+            }
+        };
+
+        visitor
+            .visit_enum(EnumAccess { variant, value })
+            .map_err(|e| self.locate(e))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqAccess<'de> {
+    iter: std::slice::Iter<'de, SpannedValue>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.iter
+            .next()
+            .map(|value| seed.deserialize(value))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapAccess<'de> {
+    iter: std::vec::IntoIter<(&'de String, &'de SpannedValue)>,
+    value: Option<&'de SpannedValue>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+
+                seed.deserialize(BorrowedStrDeserializer::new(key))
+                    .map(Some)
+                    // The key has no span of its own, the error is located at its value
+                    .map_err(|e| value.locate(e))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumAccess<'de> {
+    variant: &'de str,
+    value: Option<&'de SpannedValue>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+    type Error = Error;
+    type Variant = VariantAccess<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess<'de>), Error> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<Error>::new(self.variant))?;
+
+        Ok((variant, VariantAccess { value: self.value }))
+    }
+}
+
+struct VariantAccess<'de> {
+    value: Option<&'de SpannedValue>,
+}
+
+impl<'de> VariantAccess<'de> {
+    fn value(self) -> Result<&'de SpannedValue, Error> {
+        self.value.ok_or_else(|| {
+            de::Error::invalid_type(de::Unexpected::UnitVariant, &"a variant with a value")
+        })
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            None => Ok(()),
+            Some(value) => de::Deserialize::deserialize(value),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.value()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self.value()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self.value()?, visitor)
+    }
+}
+
+/// The size hint comes from the deserialized format and can't be trusted: like serde's
+/// `size_hint::cautious`, it's capped so a wrong hint can't allocate a huge array upfront
+const MAX_PREALLOCATED: usize = 4096;
+
+/// Builds a [SpannedValue] from any deserializer. The spans can't be known, they are left to
+/// [Position::default]
+struct SpannedValueVisitor;
+
+impl SpannedValueVisitor {
+    fn value(value: Value) -> SpannedValue {
+        value.into()
+    }
+}
+
+impl<'de> Visitor<'de> for SpannedValueVisitor {
+    type Value = SpannedValue;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any json value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<SpannedValue, E> {
+        Ok(Self::value(Value::Bool(v)))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<SpannedValue, E> {
+        let number = match u64::try_from(v) {
+            Ok(v) => Number::PosInt(v),
+            Err(_) => Number::NegInt(v),
+        };
+
+        Ok(Self::value(Value::Number(number)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<SpannedValue, E> {
+        Ok(Self::value(Value::Number(Number::PosInt(v))))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<SpannedValue, E> {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => Ok(Self::value(Value::Number(Number::Raw(v.to_string())))),
+        }
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<SpannedValue, E> {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Ok(Self::value(Value::Number(Number::Raw(v.to_string())))),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<SpannedValue, E> {
+        Ok(Self::value(Value::Number(Number::Float(v))))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<SpannedValue, E> {
+        Ok(Self::value(Value::String(v.to_string())))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<SpannedValue, E> {
+        Ok(Self::value(Value::String(v)))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<SpannedValue, E> {
+        Ok(Self::value(Value::Null))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<SpannedValue, E> {
+        Ok(Self::value(Value::Null))
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<SpannedValue, D::Error> {
+        SpannedValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<SpannedValue, A::Error> {
+        let mut array = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(MAX_PREALLOCATED));
+
+        while let Some(element) = seq.next_element()? {
+            array.push(element);
+        }
+
+        Ok(Self::value(Value::Array(array)))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<SpannedValue, A::Error> {
+        let mut obj = Map::default();
+
+        while let Some((key, value)) = map.next_entry::<String, SpannedValue>()? {
+            obj.insert(key, value);
+        }
+
+        Ok(Self::value(Value::Object(obj)))
+    }
+}
+
+impl<'de> Deserialize<'de> for SpannedValue {
+    /// Reconstructs the value tree from any format supported by serde, so a [SpannedValue] can be
+    /// a field of a deserialized struct. The spans are unknown and left to [Position::default]
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(SpannedValueVisitor)
+    }
+}
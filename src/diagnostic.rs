@@ -0,0 +1,130 @@
+use crate::location::bom_len;
+use crate::value::{Position, TextRange};
+
+/// Renders the lines covered by `span`, surrounded by `context_lines` lines before and after,
+/// with the span underlined:
+/// ```text
+/// 2 |     "name": "app",
+/// 3 |     "version": 1,
+///   |                ^
+/// 4 | }
+/// ```
+pub fn codeframe(source: &str, span: &TextRange, context_lines: usize) -> String {
+    let first = span.start.line.saturating_sub(context_lines).max(1);
+    let last = span.end.line + context_lines;
+    let width = last.to_string().len();
+
+    let mut frame = String::new();
+
+    for (i, line) in source[bom_len(source)..].lines().enumerate() {
+        let number = i + 1;
+
+        if number < first {
+            continue;
+        }
+        if number > last {
+            break;
+        }
+
+        frame.push_str(&format!("{:>width$} | {}\n", number, line, width = width));
+
+        if number < span.start.line || number > span.end.line {
+            continue;
+        }
+
+        // The columns start at 1, a span built by hand could still start at 0
+        let from = if number == span.start.line {
+            span.start.col.max(1)
+        } else {
+            1
+        };
+        let to = if number == span.end.line {
+            span.end.col
+        } else {
+            line.chars().count()
+        };
+
+        if to >= from {
+            frame.push_str(&format!(
+                "{:>width$} | {}{}\n",
+                "",
+                " ".repeat(from - 1),
+                "^".repeat(to - from + 1),
+                width = width
+            ));
+        }
+    }
+
+    frame
+}
+
+/// Returns the text of the `line` of `source`, without its line break.
+/// Use a [LineIndex] to look up several lines of the same source
+pub fn line_text(source: &str, line: usize) -> Option<&str> {
+    LineIndex::new(source).line(line)
+}
+
+/// The byte offset where each line of a source starts, computed once so the lookups
+/// don't need to go through the source again
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(bom_len(source))
+            .chain(memchr::memchr_iter(b'\n', source.as_bytes()).map(|i| i + 1))
+            .collect();
+
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// The byte range of the `line`, starting at 1, with its line break
+    fn range(&self, line: usize) -> Option<std::ops::Range<usize>> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or(self.source.len());
+
+        Some(start..end)
+    }
+
+    /// Returns the text of the `line`, starting at 1, without its line break
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        let text = &self.source[self.range(line)?];
+        let text = text.strip_suffix('\n').unwrap_or(text);
+
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    /// Returns the position of the char starting at the byte `offset`. The offset can be the
+    /// length of the source, the position is then right after the last char
+    pub fn offset_to_position(&self, offset: usize) -> Option<Position> {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        // `None` for an offset inside the BOM
+        let start = self.line_starts[line.checked_sub(1)?];
+
+        Some(Position {
+            line,
+            col: self.source.get(start..offset)?.chars().count() + 1,
+            byte_offset: offset,
+        })
+    }
+
+    /// Returns the byte offset of the char located at `position`
+    pub fn position_to_offset(&self, position: &Position) -> Option<usize> {
+        let range = self.range(position.line)?;
+
+        self.source[range.clone()]
+            .char_indices()
+            .nth(position.col.checked_sub(1)?)
+            .map(|(i, _)| range.start + i)
+    }
+}
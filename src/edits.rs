@@ -0,0 +1,155 @@
+use crate::diagnostic::LineIndex;
+use crate::value::Position;
+use std::ops::Range;
+
+/// Replaces a part of a source by `replacement`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextEdit {
+    /// Position of the first replaced char
+    pub start: Position,
+    /// Position right after the last replaced char. It's equal to `start` for an insertion
+    pub end: Position,
+    pub replacement: String,
+}
+
+/// The tokens of a json, and the whitespaces around them: `gaps[i]` precedes `tokens[i]`,
+/// and the last gap follows the last token
+struct Tokens<'a> {
+    source: &'a str,
+    tokens: Vec<Range<usize>>,
+    gaps: Vec<Range<usize>>,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(source: &'a str) -> Self {
+        let bytes = source.as_bytes();
+        let is_space = |b: u8| matches!(b, b' ' | b'\t' | b'\r' | b'\n');
+        let is_delimiter = |b: u8| is_space(b) || b"{}[],:\"".contains(&b);
+
+        let mut tokens = Vec::new();
+        let mut gaps = Vec::new();
+        let mut i = 0;
+
+        loop {
+            let gap_start = i;
+            while i < bytes.len() && is_space(bytes[i]) {
+                i += 1;
+            }
+            gaps.push(gap_start..i);
+
+            if i == bytes.len() {
+                break;
+            }
+
+            let token_start = i;
+            i += 1;
+
+            match bytes[token_start] {
+                b'"' => {
+                    while i < bytes.len() && bytes[i] != b'"' {
+                        i += if bytes[i] == b'\\' {
+                            // The escaped char can take several bytes, the token must not end
+                            // inside it
+                            source[i + 1..].chars().next().map_or(1, char::len_utf8) + 1
+                        } else {
+                            1
+                        };
+                    }
+                    i = (i + 1).min(bytes.len());
+                }
+                b'{' | b'}' | b'[' | b']' | b',' | b':' => (),
+                _ => {
+                    while i < bytes.len() && !is_delimiter(bytes[i]) {
+                        i += 1;
+                    }
+                }
+            }
+
+            tokens.push(token_start..i);
+        }
+
+        Self {
+            source,
+            tokens,
+            gaps,
+        }
+    }
+
+    fn token(&self, i: usize) -> &'a str {
+        &self.source[self.tokens[i].clone()]
+    }
+
+    fn gap(&self, i: usize) -> &'a str {
+        &self.source[self.gaps[i].clone()]
+    }
+}
+
+/// Returns the edits turning `original` into `formatted`, usually the same json re-serialized,
+/// so an editor can apply a formatting without replacing the whole document.
+///
+/// The whitespaces are compared between each token, and the tokens that changed are replaced
+/// by a single edit spanning from the first to the last changed token
+/// ```ignore
+/// use spanned_json_parser::minimal_edits;
+///
+/// fn main() {
+///     let original = r#"{"a":1}"#;
+///     let formatted = "{\n  \"a\": 1\n}";
+///
+///     println!("Edits: {:#?}", minimal_edits(original, formatted));
+/// }
+/// ```
+pub fn minimal_edits(original: &str, formatted: &str) -> Vec<TextEdit> {
+    let old = Tokens::new(original);
+    let new = Tokens::new(formatted);
+
+    let (n, m) = (old.tokens.len(), new.tokens.len());
+
+    let prefix = (0..n.min(m))
+        .take_while(|&i| old.token(i) == new.token(i))
+        .count();
+    let suffix = (0..n.min(m) - prefix)
+        .take_while(|&i| old.token(n - 1 - i) == new.token(m - 1 - i))
+        .count();
+
+    let mut replaced: Vec<(Range<usize>, &str)> = Vec::new();
+
+    // The gaps around the changed tokens are compared on their own, unless tokens have only
+    // been added or removed, there is then nothing to surround
+    let around = n > prefix + suffix && m > prefix + suffix;
+    let first_gap = if around { prefix + 1 } else { prefix };
+
+    for i in 0..first_gap {
+        replaced.push((old.gaps[i].clone(), new.gap(i)));
+    }
+
+    let changed = |tokens: &Tokens, count: usize| {
+        if around {
+            tokens.tokens[prefix].start..tokens.tokens[count - suffix - 1].end
+        } else {
+            tokens.gaps[prefix].start..tokens.gaps[count - suffix].end
+        }
+    };
+    let new_changed = changed(&new, m);
+    replaced.push((changed(&old, n), &formatted[new_changed]));
+
+    let last_gap = if around { 0 } else { 1 };
+
+    for i in last_gap..=suffix {
+        replaced.push((old.gaps[n - suffix + i].clone(), new.gap(m - suffix + i)));
+    }
+
+    let index = LineIndex::new(original);
+
+    replaced
+        .into_iter()
+        .filter(|(range, replacement)| &original[range.clone()] != *replacement)
+        .filter_map(|(range, replacement)| {
+            Some(TextEdit {
+                start: index.offset_to_position(range.start)?,
+                end: index.offset_to_position(range.end)?,
+                replacement: replacement.to_string(),
+            })
+        })
+        .collect()
+}
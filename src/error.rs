@@ -1,9 +1,13 @@
-use crate::{parser::Span, value::Position};
+use crate::{
+    parser::Span,
+    value::{Position, ValueType},
+};
 use nom::error::{ErrorKind, FromExternalError, ParseError};
+use std::fmt::Display;
 use std::num::ParseFloatError;
 use std::num::ParseIntError;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Kind {
     MissingQuote,
     MissingArrayBracket,
@@ -12,8 +16,25 @@ pub enum Kind {
     InvalidKey(String),
     MissingChar(char),
     MissingColon,
-    CharsAfterRoot(String),
-    NotAnHex(String),
+    /// Some chars remain after the root value. The error spans `token`, the first value or word of
+    /// `trailing`, which holds all the remaining chars. `parseable` is true when they start with a
+    /// valid value
+    CharsAfterRoot {
+        token: String,
+        trailing: String,
+        parseable: bool,
+    },
+    /// The chars after the root are a valid value. The error spans the second value,
+    /// and the span of the root is kept here
+    MultipleRootValues {
+        root_start: Position,
+        root_end: Position,
+    },
+    /// The 4 chars following `\u` in a string, starting at `position`, are not an hex number
+    NotAnHex {
+        raw: String,
+        position: Position,
+    },
     NotAString,
     NotABool,
     NotANull,
@@ -22,12 +43,191 @@ pub enum Kind {
     NotANumber,
     InvalidValue(String),
     TrailingComma,
+    /// The root value type is not part of [ParseOptions::allowed_roots](crate::ParseOptions::allowed_roots)
+    DisallowedRootType {
+        found: ValueType,
+    },
+    /// The decimal part of the number ends with zeros, reported when
+    /// [ParseOptions::reject_trailing_decimal_zeros](crate::ParseOptions::reject_trailing_decimal_zeros) is set
+    NonCanonicalNumber(String),
+    /// The string can't be decoded by [SpannedValue::as_base64_bytes](crate::SpannedValue::as_base64_bytes)
+    InvalidBase64,
+    /// The bytes given to [parse_bytes](crate::parse_bytes) are not encoded in UTF-8
+    WrongEncoding {
+        detected: Encoding,
+    },
+    /// The bytes given to [parse_bytes](crate::parse_bytes) are not valid UTF-8
+    InvalidUtf8,
+    /// The key is already used in the object, reported when
+    /// [ParseOptions::error_on_duplicate_keys](crate::ParseOptions::error_on_duplicate_keys) is set.
+    /// The error spans the second occurrence of the key
+    DuplicateKey(String),
+    /// The element is equal to a previous element of the array, reported when
+    /// [ParseOptions::require_unique_array_items](crate::ParseOptions::require_unique_array_items)
+    /// is set. The error spans the repeated element
+    DuplicateArrayItem,
+    /// A `/*` comment isn't closed, reported when
+    /// [ParseOptions::allow_comments](crate::ParseOptions::allow_comments) is set.
+    /// The error spans from the start of the comment to the end of the input
+    UnterminatedComment,
+    /// A control char is written as is in a string, instead of being escaped. The error spans
+    /// the char, it isn't reported when
+    /// [ParseOptions::allow_control_characters](crate::ParseOptions::allow_control_characters) is set
+    ControlCharacterInString(char),
+    /// A `\u` escape is a lone surrogate: a high surrogate not followed by a low one, or a low
+    /// surrogate alone. The error spans the escape
+    InvalidSurrogatePair(String),
+    /// A [SpannedValue](crate::SpannedValue) can't be deserialized by
+    /// [from_spanned_value](crate::from_spanned_value). The error spans the value
+    Deserialize(String),
+    /// The reader given to [parse_stream](crate::parse_stream) failed
+    Io(String),
+    /// The integer part of the number starts with a `0` followed by other digits (ex: `01`).
+    /// The error spans the number, it isn't reported when
+    /// [ParseOptions::allow_leading_zeros](crate::ParseOptions::allow_leading_zeros) is set
+    LeadingZero(String),
+    /// The string, or the key, is longer than
+    /// [ParseOptions::max_string_length](crate::ParseOptions::max_string_length) once decoded.
+    /// The error spans from the opening quote to the first char over the limit
+    StringTooLong {
+        max: usize,
+    },
+    /// The input is longer than [ParseOptions::max_total_bytes](crate::ParseOptions::max_total_bytes),
+    /// it's rejected before being parsed
+    InputTooLarge {
+        size: usize,
+        max: usize,
+    },
+    /// The number doesn't follow the grammar of RFC 8259, ex: `+1`, `.5`, `1.` or `1.e5`.
+    /// The error spans the number
+    InvalidNumber(String),
     NomError(nom::error::ErrorKind),
     // Used when an error will be remaped
     ToBeDefined,
 }
 
-#[derive(Debug)]
+/// The encodings, other than UTF-8, detected by [parse_bytes](crate::parse_bytes)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Encoding {
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Utf16Le => write!(f, "UTF-16 LE"),
+            Self::Utf16Be => write!(f, "UTF-16 BE"),
+            Self::Utf32Le => write!(f, "UTF-32 LE"),
+            Self::Utf32Be => write!(f, "UTF-32 BE"),
+        }
+    }
+}
+
+/// Maps a [Kind] to the message shown to the user. Implement it to translate the messages
+/// ```ignore
+/// use spanned_json_parser::error::{English, Kind, MessageProvider};
+///
+/// struct French;
+///
+/// impl MessageProvider for French {
+///     fn message(&self, kind: &Kind) -> String {
+///         match kind {
+///             Kind::MissingComma => "virgule manquante".into(),
+///             kind => English.message(kind),
+///         }
+///     }
+/// }
+/// ```
+pub trait MessageProvider {
+    fn message(&self, kind: &Kind) -> String;
+}
+
+/// The default [MessageProvider]
+pub struct English;
+
+impl MessageProvider for English {
+    fn message(&self, kind: &Kind) -> String {
+        // No wildcard, so a new kind can't be added without its message
+        match kind {
+            Kind::MissingQuote => "missing closing quote".into(),
+            Kind::MissingArrayBracket => "missing closing bracket `]`".into(),
+            Kind::MissingComma => "missing comma".into(),
+            Kind::MissingObjectBracket => "missing closing brace `}`".into(),
+            Kind::InvalidKey(key) if key.is_empty() => "missing key".into(),
+            Kind::InvalidKey(key) => format!("invalid key `{}`, keys must be strings", key),
+            Kind::MissingChar(c) => format!("missing char `{}`", c),
+            Kind::MissingColon => "missing colon after key".into(),
+            Kind::CharsAfterRoot { token, .. } => {
+                format!("unexpected chars after the root value: `{}`", token)
+            }
+            Kind::MultipleRootValues { root_end, .. } => format!(
+                "only one root value is allowed, but another one starts after the root ending at line {}, column {}",
+                root_end.line, root_end.col
+            ),
+            Kind::NotAnHex { raw, .. } => format!("`{}` is an invalid hex number", raw),
+            Kind::NotAString => "expected a string".into(),
+            Kind::NotABool => "expected a bool".into(),
+            Kind::NotANull => "expected null".into(),
+            Kind::NotAnObject => "expected an object".into(),
+            Kind::NotAnArray => "expected an array".into(),
+            Kind::NotANumber => "expected a number".into(),
+            Kind::InvalidValue(value) => format!("invalid value `{}`", value),
+            Kind::TrailingComma => "trailing comma is not allowed".into(),
+            Kind::DisallowedRootType { found } => {
+                format!("{:?} is not allowed as root value", found)
+            }
+            Kind::NonCanonicalNumber(number) => {
+                format!("`{}` has trailing zeros in its decimal part", number)
+            }
+            Kind::InvalidBase64 => "invalid base64 string".into(),
+            Kind::WrongEncoding { detected } => format!(
+                "the input is encoded in {}, it must be transcoded to UTF-8",
+                detected
+            ),
+            Kind::InvalidUtf8 => "invalid UTF-8".into(),
+            Kind::DuplicateKey(key) => format!("duplicate key `{}`", key),
+            Kind::DuplicateArrayItem => "the items of the array must be unique".into(),
+            Kind::UnterminatedComment => "unterminated block comment, missing `*/`".into(),
+            Kind::ControlCharacterInString(c) => format!(
+                "control char `\\u{:04X}` must be escaped in a string",
+                *c as u32
+            ),
+            Kind::Deserialize(message) => message.clone(),
+            Kind::Io(message) => format!("failed to read the input: {}", message),
+            Kind::InvalidNumber(number) => format!("invalid number `{}`", number),
+            Kind::StringTooLong { max } => {
+                format!("the string is longer than the limit of {} bytes", max)
+            }
+            Kind::InputTooLarge { size, max } => format!(
+                "the input is {} bytes, more than the limit of {} bytes",
+                size, max
+            ),
+            Kind::LeadingZero(number) => {
+                format!("`{}` has a leading zero, which is not allowed", number)
+            }
+            Kind::InvalidSurrogatePair(escape) => {
+                format!("`{}` is a lone surrogate, it must be part of a pair", escape)
+            }
+            Kind::NomError(kind) => format!("unexpected syntax ({})", kind.description()),
+            Kind::ToBeDefined => "unknown error".into(),
+        }
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", English.message(self))
+    }
+}
+
+/// The kinds don't carry the location of the error, log the [Error] instead: its [Display]
+/// includes the position (ex: `invalid value `tru` at line 1, column 2`). For a missing closing
+/// bracket, it's the position of the opening one
+/// (ex: `expected `}` to close the object opened at line 1, column 1`)
+#[derive(Debug, Clone)]
 pub struct Error {
     pub start: Position,
     pub end: Position,
@@ -42,8 +242,39 @@ impl Error {
             kind: value,
         }
     }
+
+    /// Returns the message of the error, translated by `provider`
+    pub fn message(&self, provider: &dyn MessageProvider) -> String {
+        provider.message(&self.kind)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The error of a missing closing bracket starts at the opening one
+        let closing = match self.kind {
+            Kind::MissingObjectBracket => Some(("}", "object")),
+            Kind::MissingArrayBracket => Some(("]", "array")),
+            _ => None,
+        };
+
+        match closing {
+            Some((bracket, container)) => write!(
+                f,
+                "expected `{}` to close the {} opened at line {}, column {}",
+                bracket, container, self.start.line, self.start.col
+            ),
+            None => write!(
+                f,
+                "{} at line {}, column {}",
+                self.kind, self.start.line, self.start.col
+            ),
+        }
+    }
 }
 
+impl std::error::Error for Error {}
+
 impl Default for Error {
     fn default() -> Self {
         Self {
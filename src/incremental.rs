@@ -0,0 +1,156 @@
+use crate::location::position_to_offset;
+use crate::options::DEFAULT_OPTIONS;
+use crate::parser::{parse, parse_value_at, ParseResult};
+use crate::value::{PathSegment, Position, SpannedValue, Value};
+
+/// Describes a text edit that has been applied to a previously parsed source.
+///
+/// Positions follow the same convention as [Position]: lines and columns start at 1
+/// and columns are counted in chars. Their byte offsets are used to shift the byte offsets
+/// of the values located after the edit
+#[derive(Debug, PartialEq, Clone)]
+pub struct Edit {
+    /// Position of the first replaced char
+    pub start: Position,
+    /// Position right after the last replaced char, in the old source
+    pub old_end: Position,
+    /// Position right after the inserted text, in the new source
+    pub new_end: Position,
+}
+
+fn precedes_or_eq(a: &Position, b: &Position) -> bool {
+    (a.line, a.col) <= (b.line, b.col)
+}
+
+fn contains(value: &SpannedValue, edit: &Edit) -> bool {
+    // The end position is the last char of the value, an edit ending right after it can
+    // still modify the value (ex: appending a digit to a number)
+    let after_end = Position {
+        line: value.end.line,
+        col: value.end.col + 1,
+        byte_offset: value.end.byte_offset + 1,
+    };
+
+    precedes_or_eq(&value.start, &edit.start) && precedes_or_eq(&edit.old_end, &after_end)
+}
+
+fn shift(position: &Position, edit: &Edit) -> Position {
+    if position.line == edit.old_end.line {
+        Position {
+            line: edit.new_end.line,
+            col: edit.new_end.col + position.col - edit.old_end.col,
+            byte_offset: position.byte_offset + edit.new_end.byte_offset - edit.old_end.byte_offset,
+        }
+    } else {
+        Position {
+            line: position.line + edit.new_end.line - edit.old_end.line,
+            col: position.col,
+            byte_offset: position.byte_offset + edit.new_end.byte_offset - edit.old_end.byte_offset,
+        }
+    }
+}
+
+/// Shifts every position located after `threshold`
+fn shift_after(value: &mut SpannedValue, threshold: &Position, edit: &Edit) {
+    if !precedes_or_eq(&value.start, threshold) {
+        value.start = shift(&value.start, edit);
+    }
+    if !precedes_or_eq(&value.end, threshold) {
+        value.end = shift(&value.end, edit);
+    }
+    if let Some(key_span) = &mut value.key_span {
+        if !precedes_or_eq(&key_span.start, threshold) {
+            key_span.start = shift(&key_span.start, edit);
+            key_span.end = shift(&key_span.end, edit);
+        }
+    }
+
+    match &mut value.value {
+        Value::Array(array) => array
+            .iter_mut()
+            .for_each(|v| shift_after(v, threshold, edit)),
+        Value::Object(obj) => obj
+            .values_mut()
+            .for_each(|v| shift_after(v, threshold, edit)),
+        _ => (),
+    }
+}
+
+/// Finds the path to the deepest value, excluding the root, containing the edit
+fn find_path(value: &SpannedValue, edit: &Edit, path: &mut Vec<PathSegment>) {
+    match &value.value {
+        Value::Array(array) => {
+            if let Some((i, v)) = array.iter().enumerate().find(|(_, v)| contains(v, edit)) {
+                path.push(PathSegment::Index(i));
+                find_path(v, edit, path);
+            }
+        }
+        Value::Object(obj) => {
+            if let Some((k, v)) = obj.iter().find(|(_, v)| contains(v, edit)) {
+                path.push(PathSegment::Key(k.clone()));
+                find_path(v, edit, path);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn get_mut<'a>(value: &'a mut SpannedValue, path: &[PathSegment]) -> Option<&'a mut SpannedValue> {
+    path.iter()
+        .try_fold(value, |value, step| match (step, &mut value.value) {
+            (PathSegment::Index(i), Value::Array(array)) => array.get_mut(*i),
+            (PathSegment::Key(k), Value::Object(obj)) => obj.get_mut(k),
+            _ => None,
+        })
+}
+
+/// Re-parses `source` after an [Edit], reusing the parts of `previous` that haven't been touched.
+///
+/// Only the deepest value containing the edit is parsed again, its siblings are moved
+/// into the new tree and only have their positions shifted. When the edit can't be
+/// isolated in a single value (ex: a comma has been removed, or the root itself
+/// is modified), it falls back to a full [parse]
+/// ```ignore
+/// use spanned_json_parser::{parse, reparse, Edit, Position};
+///
+/// fn main() {
+///     let previous = parse(r#"{"a": [1, 2], "b": 1}"#).unwrap();
+///
+///     let edit = Edit {
+///         start: Position { line: 1, col: 20, byte_offset: 19 },
+///         old_end: Position { line: 1, col: 21, byte_offset: 20 },
+///         new_end: Position { line: 1, col: 22, byte_offset: 21 },
+///     };
+///
+///     let parsed = reparse(previous, r#"{"a": [1, 2], "b": 12}"#, &edit);
+///
+///     println!("Parsed: {:#?}", parsed);
+/// }
+/// ```
+pub fn reparse(mut previous: SpannedValue, source: &str, edit: &Edit) -> ParseResult {
+    let mut path = Vec::new();
+    find_path(&previous, edit, &mut path);
+
+    let (start, end) = match get_mut(&mut previous, &path) {
+        Some(target) if !path.is_empty() => (target.start.clone(), target.end.clone()),
+        _ => return parse(source),
+    };
+
+    let mut value = match position_to_offset(source, &start)
+        .map(|i| parse_value_at(&source[i..], &start, &DEFAULT_OPTIONS))
+    {
+        // If the value doesn't end where expected, the edit has changed the structure around it
+        Some(Ok((value, _))) if value.end == shift(&end, edit) => value,
+        _ => return parse(source),
+    };
+
+    shift_after(&mut previous, &end, edit);
+
+    if let Some(target) = get_mut(&mut previous, &path) {
+        // The key is located before the value, so it hasn't been modified
+        value.key_span = target.key_span.take();
+        *target = value;
+    }
+
+    Ok(previous)
+}
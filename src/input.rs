@@ -1,3 +1,7 @@
+use crate::intern::Interner;
+use crate::options::ParseOptions;
+use crate::recovery::Recover;
+use crate::value::{ObjectKey, Position};
 use bytecount::num_chars;
 use memchr::Memchr;
 use nom::{
@@ -5,6 +9,7 @@ use nom::{
     InputTakeAtPosition, Offset, ParseTo, Slice,
 };
 use std::{
+    borrow::Cow,
     ops::{Range, RangeFrom, RangeTo},
     str::{CharIndices, Chars, FromStr},
 };
@@ -14,16 +19,55 @@ pub struct Input<'a> {
     pub data: &'a str,
     line: usize,
     col: usize,
+    byte_offset: usize,
+    /// The length in bytes of the char preceding the input, to go back to it
+    previous_char_len: usize,
+    /// The column of the char preceding the input. When it's a line break, it's on the previous
+    /// line, so its column can't be found from the one of the input
+    previous_col: usize,
+    pub options: &'a ParseOptions,
+    /// When set, the parser reports its errors here and tries to continue after them
+    pub(crate) recovery: Option<&'a dyn Recover>,
+    /// When set, the equal keys of the objects share their storage
+    pub(crate) keys: Option<&'a Interner>,
 }
 
 impl<'a> Input<'a> {
-    pub fn new(data: &'a str) -> Self {
+    pub fn new(data: &'a str, options: &'a ParseOptions) -> Self {
         Self {
             data,
             line: 1,
             col: 1,
+            byte_offset: 0,
+            previous_char_len: 0,
+            previous_col: 0,
+            options,
+            recovery: None,
+            keys: None,
         }
     }
+
+    /// Creates an input whose first char is located at `start`,
+    /// used when parsing a fragment of a bigger document
+    pub fn at(data: &'a str, start: &Position, options: &'a ParseOptions) -> Self {
+        Self {
+            data,
+            line: start.line,
+            col: start.col,
+            byte_offset: start.byte_offset,
+            previous_char_len: 0,
+            previous_col: 0,
+            options,
+            recovery: None,
+            keys: None,
+        }
+    }
+
+    /// Turns a parsed key into the key of a [Map](crate::Map)
+    pub(crate) fn key<K: ObjectKey>(&self, key: Cow<str>) -> K {
+        K::from_parsed(key, self.keys)
+    }
+
     pub fn location_line(&self) -> usize {
         self.line
     }
@@ -32,6 +76,29 @@ impl<'a> Input<'a> {
         self.col
     }
 
+    /// The offset, in bytes, of the input in the document
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// The offset, in bytes, of the char preceding the input in the document
+    pub fn previous_byte_offset(&self) -> usize {
+        self.byte_offset - self.previous_char_len
+    }
+
+    /// The line and column of the char preceding the input in the document. Without such char,
+    /// it's the position of the input
+    pub fn previous_location(&self) -> (usize, usize) {
+        if self.previous_char_len == 0 {
+            (self.line, self.col)
+        } else if self.col == 1 {
+            // Only a line break brings the input back to the first column
+            (self.line - 1, self.previous_col)
+        } else {
+            (self.line, self.previous_col)
+        }
+    }
+
     pub fn fragment(&self) -> &'a str {
         self.data
     }
@@ -211,11 +278,7 @@ impl<'a> Slice<Range<usize>> for Input<'a> {
     fn slice(&self, range: Range<usize>) -> Self {
         let next_data = self.data.slice(range);
 
-        Self {
-            data: next_data,
-            line: 0,
-            col: 1,
-        }
+        self.slice_common(next_data)
     }
 }
 
@@ -230,6 +293,12 @@ impl<'a> Input<'a> {
                 data: next_data,
                 line: self.line,
                 col: self.col,
+                byte_offset: self.byte_offset,
+                previous_char_len: self.previous_char_len,
+                previous_col: self.previous_col,
+                options: self.options,
+                recovery: self.recovery,
+                keys: self.keys,
             };
         }
 
@@ -237,23 +306,47 @@ impl<'a> Input<'a> {
 
         let mut lines_to_add = 0;
         let mut last_index = None;
+        let mut second_last_index = None;
         for i in new_line_iter {
             lines_to_add += 1;
+            second_last_index = last_index;
             last_index = Some(i);
         }
         let last_index = last_index.map(|v| v + 1).unwrap_or(0);
 
         let col = num_chars(old_data.as_bytes().slice(last_index..));
 
+        let col = if lines_to_add == 0 {
+            self.col + col
+        } else {
+            // When going to a new line, char starts at 1
+            col + 1
+        };
+
+        let previous_col = if last_index != offset {
+            col - 1
+        } else if lines_to_add == 1 {
+            // The line break ends the line the input was on
+            self.col + num_chars(old_data.as_bytes()) - 1
+        } else {
+            // The line break is the last char of its line, and is counted with it
+            num_chars(
+                old_data
+                    .as_bytes()
+                    .slice(second_last_index.map_or(0, |i| i + 1)..),
+            )
+        };
+
         Self {
             data: next_data,
             line: self.line + lines_to_add,
-            col: if lines_to_add == 0 {
-                self.col + col
-            } else {
-                // When going to a new line, char starts at 1
-                col + 1
-            },
+            col,
+            byte_offset: self.byte_offset + offset,
+            previous_char_len: old_data.chars().next_back().map_or(0, char::len_utf8),
+            previous_col,
+            options: self.options,
+            recovery: self.recovery,
+            keys: self.keys,
         }
     }
 }
@@ -0,0 +1,47 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Keeps the keys of a document, so the equal keys share the same storage instead of each being
+/// allocated. Used by [parse_interned](crate::parse_interned)
+#[derive(Debug, Default)]
+pub struct Interner {
+    keys: RefCell<HashSet<Arc<str>>>,
+}
+
+impl Interner {
+    pub fn intern(&self, key: &str) -> Arc<str> {
+        let mut keys = self.keys.borrow_mut();
+
+        if let Some(key) = keys.get(key) {
+            return key.clone();
+        }
+
+        let key = Arc::from(key);
+        keys.insert(Arc::clone(&key));
+
+        key
+    }
+}
+
+/// Turns the keys read by the parser into the keys of a [GenericMap](crate::GenericMap). The module is
+/// private, so [ObjectKey](crate::ObjectKey) can't be implemented outside of the crate
+pub trait Sealed: Sized {
+    fn from_parsed(key: Cow<str>, interner: Option<&Interner>) -> Self;
+}
+
+impl Sealed for String {
+    fn from_parsed(key: Cow<str>, _interner: Option<&Interner>) -> Self {
+        key.into_owned()
+    }
+}
+
+impl Sealed for Arc<str> {
+    fn from_parsed(key: Cow<str>, interner: Option<&Interner>) -> Self {
+        match interner {
+            Some(interner) => interner.intern(&key),
+            None => Arc::from(key),
+        }
+    }
+}
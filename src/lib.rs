@@ -15,12 +15,15 @@
 //! pub struct Position {
 //!     pub col: usize,
 //!     pub line: usize,
+//!     pub byte_offset: usize,
 //! }
 //!
 //! pub struct SpannedValue {
 //!     pub value: Value,
 //!     pub start: Position,
 //!     pub end: Position,
+//!     // Only set for the values of an object
+//!     pub key_span: Option<TextRange>,
 //! }
 //! ```
 //!
@@ -43,7 +46,7 @@
 //!
 //! ```ignore
 //! use serde::Deserialize;
-//! use spanned_json_parser::parse;
+//! use spanned_json_parser::{from_spanned_value, parse};
 //!
 //! #[derive(Deserialize)]
 //! struct Test {
@@ -55,12 +58,38 @@
 //!
 //!     let parsed = parse(json).unwrap();
 //!
-//!     let test: Test = serde_json::from_value(serde_json::to_value(parsed).unwrap()).unwrap();
+//!     let test: Test = from_spanned_value(parsed).unwrap();
 //!
 //!     println!("Test hello: {}", test.hello);
 //! }
 //! ```
 //!
+//! ### Preserving the key order
+//!
+//! By default, the entries of an object are stored in a `HashMap`, so their order is lost. Enable the `preserve_order` feature to store them in an [IndexMap](https://docs.rs/indexmap) instead: the keys are iterated and serialized in the order they appear in the source.
+//!
+//! ```toml
+//! spanned_json_parser = { version = "0.2", features = ["preserve_order"] }
+//! ```
+//!
+//! ### Sharing the object keys
+//!
+//! Each key of an object is allocated in its own `String`. When a document repeats the same keys many times (ex: an array of objects), parse it with `parse_interned`: it returns an `InternedSpannedValue`, whose keys are an `Arc<str>`, and the equal keys of the document share the same allocation. On the `citm_catalog.json` benchmark, it removes 38% of the allocations.
+//!
+//! ```ignore
+//! use spanned_json_parser::parse_interned;
+//!
+//! let parsed = parse_interned(r#"[{"id": 1}, {"id": 2}]"#).unwrap();
+//! ```
+//!
+//! ### Using it from JavaScript
+//!
+//! Enable the `wasm` feature to expose `parse_json` through [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/). It returns every value with its `start` and `end`, or an error object `{ line, col, endLine, endCol, kind }` when the input is invalid, so web editors can show the diagnostics in place.
+//!
+//! ```toml
+//! spanned_json_parser = { version = "0.2", features = ["wasm"] }
+//! ```
+//!
 //! ## Performance
 //!
 //! Here are the outputs of the benchmark. Everything was tested on a Macbook Pro M1, so keep in mind that this numbers are here to give you an idea of the performance, but might not be representative of the reality:
@@ -84,12 +113,44 @@ extern crate memchr;
 extern crate nom;
 extern crate serde;
 
+mod date;
+mod de;
+mod edits;
+mod incremental;
 mod input;
+mod intern;
+mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod parser;
+mod print;
+mod recovery;
 mod ser;
+mod stream;
+mod transform;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+pub mod diagnostic;
 pub mod error;
+pub mod location;
 pub mod value;
 
-pub use parser::parse;
+pub use de::from_spanned_value;
+pub use edits::{minimal_edits, TextEdit};
+pub use incremental::{reparse, Edit};
+pub use options::ParseOptions;
+#[cfg(feature = "rayon")]
+pub use parallel::parse_parallel;
+pub use parser::{
+    parse, parse_all, parse_all_with_options, parse_bytes, parse_interned, parse_lines,
+    parse_prefix, parse_with_options, parse_with_warnings, peek_root_type,
+};
+pub use print::{to_string, to_string_pretty};
+pub use recovery::Recovery;
+pub use ser::SpannedView;
+pub use stream::{parse_stream, ArrayStream};
+pub use transform::{parse_with_transforms, Pattern, Transform};
 pub use value::*;
+#[cfg(feature = "wasm")]
+pub use wasm::parse_json;
@@ -0,0 +1,44 @@
+use crate::diagnostic::LineIndex;
+use crate::value::Position;
+
+/// Returns the length in bytes of the UTF-8 BOM starting `source`, 0 when there's none.
+/// The BOM doesn't take a column, so the first line starts after it
+pub(crate) fn bom_len(source: &str) -> usize {
+    if source.starts_with('\u{FEFF}') {
+        '\u{FEFF}'.len_utf8()
+    } else {
+        0
+    }
+}
+
+/// Returns the byte offset of the char located at `position` in `source`, or `None` when there's
+/// no such char, the column being limited to the line. To convert many positions of the same
+/// source, use a [LineIndex] directly
+pub fn position_to_offset(source: &str, position: &Position) -> Option<usize> {
+    LineIndex::new(source).position_to_offset(position)
+}
+
+/// Returns the position of the char starting at the byte `offset` of `source`, the columns
+/// being counted in chars. Unlike [LineIndex::offset_to_position], an offset past the end of the
+/// source is moved to its end, an offset inside a char to the start of this char, and an offset
+/// inside the BOM to the start of the first line
+/// ```ignore
+/// use spanned_json_parser::location::offset_to_position;
+///
+/// fn main() {
+///     let position = offset_to_position("{\n  \"🤔\": 1}", 12);
+///
+///     assert_eq!((position.line, position.col), (2, 8));
+/// }
+/// ```
+pub fn offset_to_position(source: &str, offset: usize) -> Position {
+    let mut offset = offset.min(source.len()).max(bom_len(source));
+
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    LineIndex::new(source)
+        .offset_to_position(offset)
+        .expect("a char boundary after the BOM has a position")
+}
@@ -0,0 +1,90 @@
+use crate::value::ValueType;
+
+/// Options used by [parse_with_options](crate::parse_with_options) to customize the parsing
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// The types allowed for the root value. Any type is allowed when `None`
+    pub allowed_roots: Option<Vec<ValueType>>,
+    /// Makes the span of a value start at the whitespaces preceding it on its line,
+    /// so the indentation is part of the span
+    pub include_leading_whitespace_in_span: bool,
+    /// Rejects the numbers whose decimal part ends with zeros (ex: `1.50`), as canonical
+    /// JSON requires the shortest representation
+    pub reject_trailing_decimal_zeros: bool,
+    /// Parses the unquoted words (ex: `yes`) as strings instead of failing with
+    /// [Kind::InvalidValue](crate::error::Kind::InvalidValue). Only `true`, `false` and `null` are
+    /// literals: unlike YAML, `yes`, `no`, `on` and `off` are strings and not booleans
+    pub allow_bare_word_strings: bool,
+    /// Makes an invalid value span over the whitespaces, up to the next `,`, `]`, `}` or line
+    /// break, so `'hello world'` is reported as a whole instead of stopping at `'hello`
+    pub invalid_value_extends_to_whitespace: bool,
+    /// Fails with [Kind::DuplicateKey](crate::error::Kind::DuplicateKey) when a key appears twice
+    /// in the same object. With [parse_all](crate::parse_all_with_options), every duplicate of the
+    /// document is reported and the first occurrence is kept. When disabled, the last occurrence
+    /// overwrites the previous ones, like `serde_json` does, and keeps its own spans
+    pub error_on_duplicate_keys: bool,
+    /// Closes the arrays and objects left open at the end of the input instead of failing, to
+    /// salvage truncated documents. Each closing is reported as a warning by
+    /// [parse_with_warnings](crate::parse_with_warnings), while [parse_all](crate::parse_all_with_options)
+    /// keeps reporting them as errors
+    pub auto_close_brackets: bool,
+    /// Fails with [Kind::DuplicateArrayItem](crate::error::Kind::DuplicateArrayItem) when an
+    /// element of an array is equal to a previous one, like the `uniqueItems` of JSON Schema.
+    /// The elements are compared with [Value::value_eq](crate::Value::value_eq)
+    pub require_unique_array_items: bool,
+    /// Parses the floats into [Number::RawFloat](crate::value::Number::RawFloat), keeping their
+    /// text so they are displayed as written in the source (ex: `1e3` instead of `1000`).
+    /// [to_string](crate::to_string) also writes them as is, while the serde serializers
+    /// get the float
+    pub preserve_float_text: bool,
+    /// Allows the `//` line comments and `/* */` block comments wherever whitespaces are allowed,
+    /// like in JSONC config files. The block comments can't be nested
+    pub allow_comments: bool,
+    /// Parses the numbers that don't fit in a `u64`, an `i64` or an `f64` without losing
+    /// precision into [Number::Raw](crate::value::Number::Raw), instead of rounding them to a float.
+    /// [to_string](crate::to_string) writes them as is, while the serde serializers get a float
+    pub arbitrary_precision: bool,
+    /// Parses the `NaN`, `Infinity` and `-Infinity` tokens of JSON5 into a
+    /// [Number::Float](crate::value::Number::Float). They are passed as is to the serializers,
+    /// `serde_json` writes them as `null`
+    pub allow_non_finite_floats: bool,
+    /// Accepts the control chars (`U+0000` to `U+001F`, ex: a tab or a line break) written as is
+    /// in the strings. By default they must be escaped, and are reported with
+    /// [Kind::ControlCharacterInString](crate::error::Kind::ControlCharacterInString)
+    pub allow_control_characters: bool,
+    /// Accepts the integer parts starting with zeros (ex: `01`, parsed as `1`). By default they are
+    /// reported with [Kind::LeadingZero](crate::error::Kind::LeadingZero)
+    pub allow_leading_zeros: bool,
+    /// The maximum length in bytes of the strings and keys once decoded. A longer string fails
+    /// with [Kind::StringTooLong](crate::error::Kind::StringTooLong) as soon as the limit is
+    /// crossed, to protect the services parsing untrusted input
+    pub max_string_length: Option<usize>,
+    /// The maximum length in bytes of the input. A longer input is rejected with
+    /// [Kind::InputTooLarge](crate::error::Kind::InputTooLarge) before being parsed
+    pub max_total_bytes: Option<usize>,
+}
+
+pub(crate) static DEFAULT_OPTIONS: ParseOptions = ParseOptions {
+    allowed_roots: None,
+    include_leading_whitespace_in_span: false,
+    reject_trailing_decimal_zeros: false,
+    allow_bare_word_strings: false,
+    invalid_value_extends_to_whitespace: false,
+    error_on_duplicate_keys: false,
+    auto_close_brackets: false,
+    require_unique_array_items: false,
+    preserve_float_text: false,
+    allow_comments: false,
+    arbitrary_precision: false,
+    allow_non_finite_floats: false,
+    allow_control_characters: false,
+    allow_leading_zeros: false,
+    max_string_length: None,
+    max_total_bytes: None,
+};
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        DEFAULT_OPTIONS.clone()
+    }
+}
@@ -0,0 +1,181 @@
+use crate::options::DEFAULT_OPTIONS;
+use crate::parser::{parse, parse_value_at, ParseResult};
+use crate::value::{Position, SpannedValue, Value};
+use rayon::prelude::*;
+
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    line: usize,
+    col: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            bytes: s.as_bytes(),
+            offset: 0,
+            line: 1,
+            col: 1,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.offset).copied()
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            col: self.col,
+            byte_offset: self.offset,
+        }
+    }
+
+    fn advance(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.offset += 1;
+
+        if byte == b'\n' {
+            self.line += 1;
+            self.col = 1;
+        } else if byte & 0xC0 != 0x80 {
+            // Columns are counted in chars, so utf8 continuation bytes are skipped
+            self.col += 1;
+        }
+
+        Some(byte)
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.advance();
+        }
+    }
+
+    /// Moves to the next `,` or `]` that is not nested in a string or a container
+    fn skip_element(&mut self) -> Option<u8> {
+        let mut depth = 0usize;
+        let mut in_string = false;
+
+        loop {
+            let byte = self.peek()?;
+
+            match byte {
+                b'\\' if in_string => {
+                    self.advance();
+                }
+                b'"' => in_string = !in_string,
+                _ if in_string => (),
+                b'[' | b'{' => depth += 1,
+                b',' | b']' if depth == 0 => return Some(byte),
+                b']' | b'}' => depth = depth.checked_sub(1)?,
+                _ => (),
+            }
+
+            self.advance();
+        }
+    }
+}
+
+struct Element {
+    offset: usize,
+    end: usize,
+    start: Position,
+}
+
+/// Finds the boundaries of each element of a root array, and the positions of its brackets
+fn split_root_array(s: &str) -> Option<(Vec<Element>, Position, Position)> {
+    let mut scanner = Scanner::new(s);
+
+    scanner.skip_whitespaces();
+    let start = scanner.position();
+
+    if scanner.advance()? != b'[' {
+        return None;
+    }
+
+    let mut elements = Vec::new();
+
+    loop {
+        scanner.skip_whitespaces();
+
+        let offset = scanner.offset;
+        let position = scanner.position();
+        let delimiter = scanner.skip_element()?;
+
+        elements.push(Element {
+            offset,
+            end: scanner.offset,
+            start: position,
+        });
+
+        if delimiter == b']' {
+            break;
+        }
+
+        scanner.advance();
+    }
+
+    let end = scanner.position();
+    scanner.advance();
+    scanner.skip_whitespaces();
+
+    if scanner.peek().is_some() {
+        return None;
+    }
+
+    Some((elements, start, end))
+}
+
+/// Same as [parse], but when the root value is an array, its elements are parsed in parallel.
+///
+/// The elements boundaries are found with a quick scan of the input that only tracks strings and
+/// nesting. Whenever something looks wrong, the input is parsed again with [parse] so the returned
+/// error is exactly the same
+/// ```ignore
+/// use spanned_json_parser::parse_parallel;
+/// use std::fs;
+///
+/// fn main() {
+///     let json = fs::read_to_string("path").unwrap();
+///
+///     let parsed = parse_parallel(&json);
+///
+///     println!("Parsed: {:#?}", parsed);
+/// }
+/// ```
+pub fn parse_parallel(s: &str) -> ParseResult {
+    let (elements, start, end) = match split_root_array(s) {
+        // An empty element is either an empty array or a trailing comma
+        Some(split) if split.0.iter().all(|e| e.offset != e.end) => split,
+        _ => return parse(s),
+    };
+
+    let array = elements
+        .par_iter()
+        .map(|element| {
+            let (value, rest) = parse_value_at(
+                &s[element.offset..element.end],
+                &element.start,
+                &DEFAULT_OPTIONS,
+            )
+            .ok()?;
+
+            // Something remains after the value, so the element is invalid
+            rest.trim_start_matches([' ', '\t', '\n', '\r'])
+                .is_empty()
+                .then_some(value)
+        })
+        .collect::<Option<Vec<_>>>();
+
+    match array {
+        Some(array) => Ok(SpannedValue {
+            value: Value::Array(array),
+            start,
+            end,
+            key_span: None,
+        }),
+        None => parse(s),
+    }
+}
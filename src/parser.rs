@@ -1,27 +1,45 @@
-use crate::error::{Error, Kind};
+use crate::error::{Encoding, Error, Kind};
 use crate::input::Input;
-use crate::value::{Number, Position, SpannedValue, Value};
+use crate::intern::Interner;
+use crate::location::bom_len;
+use crate::options::{ParseOptions, DEFAULT_OPTIONS};
+use crate::recovery::{AutoClose, Collector, Recover, Recovery};
+use crate::value::{
+    GenericMap, GenericSpannedValue, GenericValue, InternedSpannedValue, Number, ObjectKey,
+    Position, SpannedValue, TextRange, ValueType,
+};
 use nom::bytes::complete::take_till;
 use nom::character::complete::digit0;
-use nom::combinator::{eof, opt};
-use nom::error::ParseError;
-use nom::multi::many_till;
+use nom::combinator::opt;
+use nom::multi::many0;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
-    character::complete::{anychar, char, multispace0, multispace1, none_of},
-    combinator::{cut, map, map_opt, map_res, value, verify},
-    multi::{fold_many0, many0, separated_list0},
-    sequence::{preceded, separated_pair, terminated},
-    Err, IResult, Parser,
+    character::complete::{anychar, char, multispace0, none_of},
+    combinator::{cut, map, map_res, value},
+    multi::fold_many0,
+    sequence::{preceded, terminated},
+    Err, IResult, Slice,
 };
+
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::num::ParseFloatError;
 
 pub type Span<'a> = Input<'a>;
 
 pub type Result<'a, R> = IResult<Span<'a>, R, Error>;
 pub type ParseResult = std::result::Result<SpannedValue, Error>;
 
+/// Where the errors about the whole input are reported
+const DOCUMENT_START: Position = Position {
+    line: 1,
+    col: 1,
+    byte_offset: 0,
+};
+
 fn take_until_delimiter(i: Span, is_key: bool) -> Result<String> {
     let mut chars = String::from(" ,]}\n");
     if is_key {
@@ -31,86 +49,73 @@ fn take_until_delimiter(i: Span, is_key: bool) -> Result<String> {
     take_till(move |c| chars.contains(c))(i).map(|(i, found)| (i, String::from(found.fragment())))
 }
 
-fn or_else<P, I, O, E, G>(mut parser: P, mut func: G) -> impl FnMut(I) -> IResult<I, O, E>
-where
-    P: Parser<I, O, E>,
-    G: FnMut(Err<E>, I) -> IResult<I, O, E>,
-    E: ParseError<I>,
-    I: Clone,
-{
-    move |i: I| {
-        let result = parser.parse(i.clone());
+/// Builds the [Kind::InvalidValue] error of a token starting with `first_char`,
+/// `i` being the input right after it
+fn invalid_value<O>(first_char: char, i: Span) -> Result<O> {
+    // The first char has already been eaten, so the span must start 1 col before
+    let start = Position::from_ahead(i);
 
-        match result {
-            Err(e) => func(e, i),
-            v => v,
-        }
-    }
-}
+    let (i, invalid_rest) = if i.options.invalid_value_extends_to_whitespace {
+        let (_, found) = take_till(|c| ",]}\n".contains(c))(i)?;
+        // The whitespaces before the delimiter are not part of the value
+        let len = found.fragment().trim_end().len();
 
-pub fn map_parser<I, O1, O2, E: ParseError<I>, F, G>(
-    mut parser: F,
-    mut applied_parser: G,
-) -> impl FnMut(I) -> IResult<I, O2, E>
-where
-    F: Parser<I, O1, E>,
-    G: FnMut((I, O1)) -> IResult<I, O2, E>,
-{
-    move |input: I| applied_parser(parser.parse(input)?)
-}
+        (i.slice(len..), found.fragment()[..len].to_string())
+    } else {
+        take_until_delimiter(i, false)?
+    };
 
-fn parse_true(i: Span) -> Result<bool> {
-    value(true, tag("rue"))(i).or_else(|_: Err<Error>| {
-        let start = Position::from_ahead(i);
+    let mut value = String::from(first_char);
+    value.push_str(&invalid_rest);
+    drop(invalid_rest);
 
-        let (i, invalid_rest) = take_until_delimiter(i, false)?;
+    Err(Err::Failure(Error::new(
+        start,
+        Position::from_ahead(i),
+        Kind::InvalidValue(value),
+    )))
+}
 
-        let mut value = String::from('t');
-        value.push_str(&invalid_rest);
-        drop(invalid_rest);
+/// Parses an unquoted word, when [ParseOptions::allow_bare_word_strings] is set
+fn bare_word<K: ObjectKey>(first_char: char, i: Span) -> Result<GenericValue<K>> {
+    let (j, rest) = take_until_delimiter(i, false)?;
+
+    let mut word = String::from(first_char);
+    word.push_str(&rest);
+
+    match word.as_str() {
+        "true" => Ok((j, GenericValue::Bool(true))),
+        "false" => Ok((j, GenericValue::Bool(false))),
+        "null" => Ok((j, GenericValue::Null)),
+        _ if word
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-') =>
+        {
+            Ok((j, GenericValue::String(word)))
+        }
+        _ => invalid_value(first_char, i),
+    }
+}
 
-        Err(Err::Failure(Error::new(
-            start,
-            Position::from_ahead(i),
-            Kind::InvalidValue(value),
-        )))
-    })
+fn parse_true(i: Span) -> Result<bool> {
+    value(true, tag("rue"))(i).or_else(|_: Err<Error>| invalid_value('t', i))
 }
 
 fn parse_false(i: Span) -> Result<bool> {
-    value(false, tag("alse"))(i).or_else(|_: Err<Error>| {
-        let start = Position::from_ahead(i);
-
-        let (i, invalid_rest) = take_until_delimiter(i, false)?;
-
-        let mut value = String::from('f');
-        value.push_str(&invalid_rest);
-        drop(invalid_rest);
-
-        Err(Err::Failure(Error::new(
-            start,
-            Position::from_ahead(i),
-            Kind::InvalidValue(value),
-        )))
-    })
+    value(false, tag("alse"))(i).or_else(|_: Err<Error>| invalid_value('f', i))
 }
 
 fn null(i: Span) -> Result<()> {
-    value((), tag("ull"))(i).or_else(|_: Err<Error>| {
-        let start = Position::from_ahead(i);
-
-        let (i, invalid_rest) = take_until_delimiter(i, false)?;
+    value((), tag("ull"))(i).or_else(|_: Err<Error>| invalid_value('n', i))
+}
 
-        let mut value = String::from('n');
-        value.push_str(&invalid_rest);
-        drop(invalid_rest);
+fn nan(i: Span) -> Result<Number> {
+    value(Number::Float(f64::NAN), tag("aN"))(i).or_else(|_: Err<Error>| invalid_value('N', i))
+}
 
-        Err(Err::Failure(Error::new(
-            start,
-            Position::from_ahead(i),
-            Kind::InvalidValue(value),
-        )))
-    })
+fn infinity(i: Span) -> Result<Number> {
+    value(Number::Float(f64::INFINITY), tag("nfinity"))(i)
+        .or_else(|_: Err<Error>| invalid_value('I', i))
 }
 
 fn u16_hex(i: Span) -> Result<u16> {
@@ -121,11 +126,15 @@ fn u16_hex(i: Span) -> Result<u16> {
         Err::Error(mut e) => {
             let mut end = e.start.clone();
             end.col += 4;
+            end.byte_offset += 4;
 
             let number = i.fragment().get(0..4).unwrap_or("");
 
             e.end = end;
-            e.kind = Kind::NotAnHex(format!("'{}' is an invalid hex number", number));
+            e.kind = Kind::NotAnHex {
+                raw: number.into(),
+                position: e.start.clone(),
+            };
 
             Err::Error(e)
         }
@@ -133,33 +142,60 @@ fn u16_hex(i: Span) -> Result<u16> {
     })
 }
 
-fn unicode_escape(i: Span) -> Result<char> {
-    map_opt(
-        alt((
-            // Not a surrogate
-            map(verify(u16_hex, |cp| !(0xD800..0xE000).contains(cp)), |cp| {
-                cp as u32
-            }),
+/// Parses the hex digits of a `\u` escape, `start` being the position of its `\`.
+/// The surrogates must come in pairs (a high one followed by a low one) to form a char
+fn unicode_escape(start: Position) -> impl FnMut(Span) -> Result<char> {
+    move |i: Span| {
+        let (j, cp) = u16_hex(i)?;
+
+        let (j, c) = match cp {
             // See https://en.wikipedia.org/wiki/UTF-16#Code_points_from_U+010000_to_U+10FFFF for details
-            map(
-                verify(
-                    separated_pair(u16_hex, tag("\\u"), u16_hex),
-                    |(high, low)| (0xD800..0xDC00).contains(high) && (0xDC00..0xE000).contains(low),
-                ),
-                |(high, low)| {
-                    let high_ten = (high as u32) - 0xD800;
+            0xD800..=0xDBFF => match preceded(tag("\\u"), u16_hex)(j) {
+                Ok((k, low)) if (0xDC00..0xE000).contains(&low) => {
+                    let high_ten = (cp as u32) - 0xD800;
                     let low_ten = (low as u32) - 0xDC00;
-                    (high_ten << 10) + low_ten + 0x10000
-                },
-            ),
-        )),
-        // Could probably be replaced with .unwrap() or _unchecked due to the verify checks
-        std::char::from_u32,
-    )(i)
+
+                    (k, std::char::from_u32((high_ten << 10) + low_ten + 0x10000))
+                }
+                _ => (j, None),
+            },
+            0xDC00..=0xDFFF => (j, None),
+            cp => (j, std::char::from_u32(cp as u32)),
+        };
+
+        match c {
+            Some(c) => Ok((j, c)),
+            None => {
+                let escape = format!("\\u{}", &i.fragment()[..4]);
+                let end = Position::from_ahead(i.slice(4..));
+
+                recover(
+                    j,
+                    Error::new(start.clone(), end, Kind::InvalidSurrogatePair(escape)),
+                )?;
+
+                Ok((j, char::REPLACEMENT_CHARACTER))
+            }
+        }
+    }
 }
 
-fn parse_char(i: Span) -> Result<char> {
-    let (i, c) = none_of("\"")(i)?;
+fn parse_char(input: Span) -> Result<char> {
+    let (i, c) = none_of("\"")(input)?;
+
+    if c < '\u{20}' && !i.options.allow_control_characters {
+        // Taken before the char, as a line break moves the input to the next line
+        let position = Position::from(input);
+
+        recover(
+            i,
+            Error::new(
+                position.clone(),
+                position,
+                Kind::ControlCharacterInString(c),
+            ),
+        )?;
+    }
 
     if c == '\\' {
         alt((
@@ -174,27 +210,97 @@ fn parse_char(i: Span) -> Result<char> {
                     _ => return Err(()),
                 })
             }),
-            preceded(char('u'), unicode_escape),
+            preceded(char('u'), unicode_escape(Position::from(input))),
         ))(i)
     } else {
         Ok((i, c))
     }
 }
 
-fn string(i: Span<'_>) -> Result<String> {
+/// Parses the content of a string after its opening quote. The content is borrowed from the
+/// input when it has nothing to decode, and only allocated otherwise
+fn string(i: Span) -> Result<Cow<str>> {
     let start = Position::from_ahead(i);
 
+    // The chars before the first escape are copied at once, so a big string is allocated
+    // with its exact size instead of growing char by char
+    let mut plain = memchr::memchr2(b'"', b'\\', i.fragment().as_bytes()).unwrap_or(i.data.len());
+
+    if !i.options.allow_control_characters {
+        // The control chars are left to `parse_char`, which reports them
+        plain = i.fragment().as_bytes()[..plain]
+            .iter()
+            .position(|b| *b < 0x20)
+            .unwrap_or(plain);
+    }
+
+    let prefix = &i.fragment()[..plain];
+    let max = i.options.max_string_length.unwrap_or(usize::MAX);
+
+    if prefix.len() > max {
+        // The error ends at the first char over the limit
+        let end = prefix
+            .char_indices()
+            .map(|(index, c)| index + c.len_utf8())
+            .find(|end| *end > max);
+
+        return Err(Err::Failure(Error::new(
+            start,
+            Position::from_ahead(i.slice(end.unwrap_or(plain)..)),
+            Kind::StringTooLong { max },
+        )));
+    }
+
+    if i.fragment()[plain..].starts_with('"') {
+        return Ok((i.slice(plain + 1..), Cow::Borrowed(prefix)));
+    }
+
+    let mut len = prefix.len();
+    let string_start = start.clone();
+
+    // Stops at the first char over the limit, the rest of the string is not decoded
+    let limited_char = move |input| {
+        let (j, c) = parse_char(input)?;
+        len += c.len_utf8();
+
+        if len > max {
+            return Err(Err::Failure(Error::new(
+                string_start.clone(),
+                Position::from_ahead(j),
+                Kind::StringTooLong { max },
+            )));
+        }
+
+        Ok((j, c))
+    };
+
     terminated(
-        fold_many0(parse_char, String::new, |mut string, c| {
-            string.push(c);
-            string
-        }),
+        fold_many0(
+            limited_char,
+            move || String::from(prefix),
+            |mut string, c| {
+                string.push(c);
+                string
+            },
+        ),
         cut(char('"')),
-    )(i)
+    )(i.slice(plain..))
+    .map(|(i, string)| (i, Cow::Owned(string)))
     .map_err(|e| match e {
+        Err::Failure(e)
+            if matches!(
+                e.kind,
+                Kind::ControlCharacterInString(_)
+                    | Kind::InvalidSurrogatePair(_)
+                    | Kind::StringTooLong { .. }
+            ) =>
+        {
+            Err::Failure(e)
+        }
         Err::Failure(mut e) => {
             e.start = start;
-            e.end.col -= 1;
+            // The error is located after the last char of the string
+            e.end = Position::from_ahead(i.slice(e.end.byte_offset - i.byte_offset()..));
             e.kind = Kind::MissingQuote;
             Err::Failure(e)
         }
@@ -202,41 +308,184 @@ fn string(i: Span<'_>) -> Result<String> {
     })
 }
 
+/// Returns the digits of a number, without its sign, exponent and the zeros around them.
+/// Two numbers with the same value have the same significant digits
+fn significant_digits(number: &str) -> String {
+    let mantissa = number.split(['e', 'E']).next().unwrap_or(number);
+
+    mantissa
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>()
+        .trim_matches('0')
+        .to_string()
+}
+
+/// Returns true when the integer part of a number, made of `first_char` and the `digits`
+/// following it, has more than one digit and starts with `0` (ex: `01` or `-01`)
+fn has_leading_zero(first_char: char, digits: &str) -> bool {
+    match first_char {
+        '0' => !digits.is_empty(),
+        '-' => digits.len() > 1 && digits.starts_with('0'),
+        _ => false,
+    }
+}
+
+/// Checks that `number` is made of an optional `-`, an integer part, and optionally a fraction
+/// and an exponent, each of them having at least one digit
+fn is_json_number(number: &str) -> bool {
+    fn digits(s: &str) -> (usize, &str) {
+        let len = s.bytes().take_while(u8::is_ascii_digit).count();
+        (len, &s[len..])
+    }
+
+    let (len, rest) = digits(number.strip_prefix('-').unwrap_or(number));
+
+    if len == 0 {
+        return false;
+    }
+
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) => match digits(fraction) {
+            (0, _) => return false,
+            (_, rest) => rest,
+        },
+        None => rest,
+    };
+
+    match rest.strip_prefix(['e', 'E']) {
+        Some(exponent) => {
+            let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            matches!(digits(exponent), (len, "") if len > 0)
+        }
+        None => rest.is_empty(),
+    }
+}
+
+/// Parses an integer that doesn't fit in an `u64` or an `i64`
+fn too_big_integer(formatted: &str, i: Span) -> std::result::Result<Number, ParseFloatError> {
+    let digits = formatted.strip_prefix('-').unwrap_or(formatted);
+
+    if i.options.arbitrary_precision
+        && !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+    {
+        Ok(Number::Raw(formatted.to_string()))
+    } else {
+        formatted.parse().map(Number::Float)
+    }
+}
+
 fn number(first_char: char) -> impl FnMut(Span) -> IResult<Span, Number, Error>
 where
 {
     move |i: Span| {
         let start = Position::from_ahead(i);
+        let after_first_char = i;
 
-        let (i, digit) = verify(digit0, |i: &Span| {
-            let frag = i.fragment();
-
-            frag.is_empty() || first_char != '0'
-        })(i)
-        .map_err(|_: Err<Error>| {
-            Err::Error(Error::new(start.clone(), start.clone(), Kind::NotANumber))
-        })?;
+        let (i, digit) = digit0(i)?;
 
         let (i, rest) = take_until_delimiter(i, false)?;
 
+        if has_leading_zero(first_char, digit.fragment()) && !i.options.allow_leading_zeros {
+            return Err(Err::Failure(Error::new(
+                start,
+                Position::from_ahead(i),
+                Kind::LeadingZero(format!("{}{}{}", first_char, digit.fragment(), rest)),
+            )));
+        }
+
+        // Fast path for integers that are too short to overflow, they are parsed without
+        // building a string
+        if rest.is_empty() && digit.fragment().len() < 18 {
+            let parse_digits = |init: u64| {
+                digit
+                    .fragment()
+                    .bytes()
+                    .fold(init, |acc, b| acc * 10 + u64::from(b - b'0'))
+            };
+
+            match first_char {
+                '-' if !digit.is_empty() => {
+                    return Ok((i, Number::NegInt(-(parse_digits(0) as i64))));
+                }
+                '0'..='9' => {
+                    return Ok((
+                        i,
+                        Number::PosInt(parse_digits(first_char as u64 - '0' as u64)),
+                    ));
+                }
+                _ => (),
+            }
+        }
+
         let formatted = format!("{}{}{}", first_char, digit.fragment(), rest);
 
+        // The float parser of Rust also accepts `inf` and `NaN`, they must not go through it
+        if !formatted
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b))
+        {
+            if i.options.allow_non_finite_floats && formatted == "-Infinity" {
+                return Ok((i, Number::Float(f64::NEG_INFINITY)));
+            }
+
+            return invalid_value(first_char, after_first_char);
+        }
+
+        // Rust parses numbers that json doesn't allow (ex: `+1`, `.5`, `1.`)
+        if !is_json_number(&formatted) {
+            return Err(Err::Failure(Error::new(
+                start,
+                Position::from_ahead(i),
+                Kind::InvalidNumber(formatted),
+            )));
+        }
+
+        if i.options.reject_trailing_decimal_zeros {
+            let decimals = formatted
+                .split_once('.')
+                .map(|(_, decimals)| decimals.split(['e', 'E']).next().unwrap_or(decimals));
+
+            if decimals.is_some_and(|d| d.ends_with('0')) {
+                return Err(Err::Failure(Error::new(
+                    start,
+                    Position::from_ahead(i),
+                    Kind::NonCanonicalNumber(formatted),
+                )));
+            }
+        }
+
         let number =
             (if formatted.contains('.') || formatted.contains('e') || formatted.contains('E') {
-                formatted.parse().map(Number::Float).map_err(|_| ())
+                formatted
+                    .parse()
+                    .map(|float: f64| {
+                        if i.options.arbitrary_precision
+                            && significant_digits(&formatted)
+                                != significant_digits(&format!("{:e}", float))
+                        {
+                            Number::Raw(formatted.clone())
+                        } else if i.options.preserve_float_text {
+                            Number::RawFloat(float, formatted.clone())
+                        } else {
+                            Number::Float(float)
+                        }
+                    })
+                    .map_err(|_| ())
             } else if first_char == '-' {
                 formatted
                     .parse()
                     .map(Number::NegInt)
                     // Parsing too big numbers into float
-                    .or_else(|_| formatted.parse().map(Number::Float))
+                    .or_else(|_| too_big_integer(&formatted, i))
                     .map_err(|_| ())
             } else {
                 formatted
                     .parse()
                     .map(Number::PosInt)
                     // Parsing too big numbers into float
-                    .or_else(|_| formatted.parse().map(Number::Float))
+                    .or_else(|_| too_big_integer(&formatted, i))
                     .map_err(|_| ())
             })
             .map_err(|_| {
@@ -251,84 +500,249 @@ where
     }
 }
 
-fn array(i: Span) -> Result<Vec<SpannedValue>> {
-    let start = Position::from_ahead(i);
+/// Skips the whitespaces, and the `//` and `/* */` comments when
+/// [ParseOptions::allow_comments] is set
+fn whitespaces(i: Span) -> Result<()> {
+    let (mut i, _) = multispace0(i)?;
+
+    if !i.options.allow_comments {
+        return Ok((i, ()));
+    }
+
+    loop {
+        let comment_len = if i.fragment().starts_with("//") {
+            i.fragment().find('\n').unwrap_or(i.data.len())
+        } else if i.fragment().starts_with("/*") {
+            match i.fragment()[2..].find("*/") {
+                Some(end) => end + 4,
+                None => {
+                    let end = i.slice(i.data.len()..);
+
+                    return Err(Err::Failure(Error::new(
+                        Position::from(i),
+                        Position::from_ahead(end),
+                        Kind::UnterminatedComment,
+                    )));
+                }
+            }
+        } else {
+            return Ok((i, ()));
+        };
+
+        (i, _) = multispace0(i.slice(comment_len..))?;
+    }
+}
+
+/// Reports `error` to the recovery handler of the input. Fails when there is no handler,
+/// or when the handler wants to stop the parsing
+fn recover(i: Span, error: Error) -> std::result::Result<(), Err<Error>> {
+    match i.recovery {
+        Some(recovery) if recovery.recover(&error) => Ok(()),
+        _ => Err(Err::Failure(error)),
+    }
+}
+
+/// Same as [recover], for a container left open at the end of the input, which can be
+/// closed with a warning
+fn auto_close(i: Span, error: Error) -> std::result::Result<(), Err<Error>> {
+    match i.recovery {
+        Some(recovery) if recovery.auto_close(&error) => Ok(()),
+        _ => recover(i, error),
+    }
+}
+
+/// Moves to the next `,` or `closing` char that is not nested in a string or a container,
+/// so an invalid element can be skipped
+fn skip_element(i: Span, closing: char) -> Span {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut chars = i.fragment().char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            _ if in_string => (),
+            '[' | '{' => depth += 1,
+            c if depth == 0 && (c == ',' || c == closing) => return i.slice(index..),
+            ']' | '}' => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+    }
+
+    i.slice(i.fragment().len()..)
+}
 
-    let (i, _) = multispace0(i)?;
+/// Parses the comma separated elements of a container up to its `closing` char.
+/// The loop stops when `element` returns an error, so it can detect the end of the container itself
+fn elements<'a, O>(
+    mut i: Span<'a>,
+    start: &Position,
+    closing: char,
+    missing_closing: Kind,
+    mut element: impl FnMut(Span<'a>) -> Result<'a, O>,
+) -> Result<'a, Vec<O>> {
+    let mut values = Vec::new();
+    let mut before_separator = i;
+    let mut before_element;
+
+    loop {
+        before_element = i;
+
+        match element(i) {
+            Ok((next, value)) => {
+                values.push(value);
+                i = next;
+            }
+            Err(Err::Error(_)) => {
+                i = before_separator;
+                break;
+            }
+            Err(Err::Failure(e)) => {
+                recover(i, e)?;
+                i = skip_element(i, closing);
+            }
+            Err(e) => return Err(e),
+        }
+
+        before_separator = i;
+
+        let (j, _) = whitespaces(i)?;
+
+        if j.starts_with(',') {
+            let (k, _) = whitespaces(j.slice(1..))?;
+
+            if k.starts_with(closing) {
+                let position = Position::from(j);
+                recover(
+                    j,
+                    Error::new(position.clone(), position, Kind::TrailingComma),
+                )?;
+
+                i = k;
+                break;
+            }
+
+            i = j.slice(1..);
+        } else if j.is_empty() || j.starts_with(closing) {
+            break;
+        } else {
+            // The error spans the element missing its comma, not the whole container
+            let (element_start, _) = whitespaces(before_element)?;
+
+            recover(
+                j,
+                Error::new(
+                    Position::from(element_start),
+                    Position::from_ahead(j),
+                    Kind::MissingComma,
+                ),
+            )?;
 
-    if i.starts_with(']') {
+            // The closing char of the parent means that this container hasn't been closed
+            if j.starts_with(']') || j.starts_with('}') {
+                return Ok((j, values));
+            }
+
+            // Recovers as if the comma was there
+            i = j;
+        }
+    }
+
+    let (i, _) = whitespaces(i)?;
+
+    if i.starts_with(closing) {
         let (i, _) = anychar(i)?;
 
+        Ok((i, values))
+    } else {
+        // The input can end right after a comma, which is part of the unclosed container
+        let (rest, _) = opt(terminated(char(','), whitespaces))(i)?;
+
+        let error = Error::new(start.clone(), Position::from_ahead(i), missing_closing);
+
+        if rest.is_empty() {
+            auto_close(i, error)?;
+
+            Ok((rest, values))
+        } else {
+            recover(i, error)?;
+
+            Ok((i, values))
+        }
+    }
+}
+
+fn array<K: ObjectKey>(i: Span) -> Result<Vec<GenericSpannedValue<K>>> {
+    let start = Position::from_ahead(i);
+
+    // The spaces before the first value are kept, so they can be part of its span
+    let (j, _) = whitespaces(i)?;
+
+    if j.starts_with(']') {
+        let (i, _) = anychar(j)?;
+
         Ok((i, Vec::new()))
-    } else if i.is_empty() {
+    } else if j.is_empty() {
         let mut end = start.clone();
         end.col += 1;
-        Err(Err::Failure(Error::new(
-            start,
-            end,
-            Kind::MissingArrayBracket,
-        )))
+        end.byte_offset += 1;
+        auto_close(j, Error::new(start, end, Kind::MissingArrayBracket))?;
+
+        Ok((j, Vec::new()))
     } else {
-        terminated(
-            separated_list0(
-                preceded(
-                    multispace0,
-                    or_else(char(','), |e: Err<Error>, i| {
-                        let (i, _) = multispace0(i)?;
-
-                        match e {
-                            Err::Error(mut e) if !i.is_empty() && !i.starts_with(']') => {
-                                e.kind = Kind::MissingComma;
-                                e.start = start.clone();
-                                e.end.col -= 1;
-
-                                Err(Err::Failure(e))
-                            }
-                            e => Err(e),
-                        }
-                    }),
-                ),
-                or_else(json_value, |e: Err<Error>, i| {
-                    // If it succeeds, it means that it's a trailing comma
-                    let _ = preceded(multispace0, char(']'))(i).map_err(|_: Err<Error>| e)?;
-
-                    Err(Err::Failure(Error::new(
-                        Position::from_ahead(i),
-                        Position::from_ahead(i),
-                        Kind::TrailingComma,
-                    )))
-                }),
-            ),
-            preceded(
-                multispace0,
-                or_else(char(']'), |e: Err<Error>, _| match e {
-                    Err::Error(mut e) => {
-                        e.kind = Kind::MissingArrayBracket;
-                        e.start = start.clone();
-                        e.end.col -= 1;
-
-                        Err(Err::Failure(e))
-                    }
-                    e => Err(e),
-                }),
-            ),
-        )(i)
+        let (i, values) = elements(i, &start, ']', Kind::MissingArrayBracket, json_value)?;
+
+        if !i.options.require_unique_array_items {
+            return Ok((i, values));
+        }
+
+        let mut unique: Vec<GenericSpannedValue<K>> = Vec::with_capacity(values.len());
+        // The equal values have the same hash, so a value is only compared with the ones
+        // sharing its hash instead of every previous one
+        let mut by_hash: HashMap<u64, Vec<usize>> = HashMap::with_capacity(values.len());
+
+        for value in values {
+            let mut hasher = DefaultHasher::new();
+            value.value.hash(&mut hasher);
+
+            let same_hash = by_hash.entry(hasher.finish()).or_default();
+
+            if same_hash
+                .iter()
+                .any(|&index| unique[index].value.value_eq(&value.value))
+            {
+                recover(
+                    i,
+                    Error::new(value.start, value.end, Kind::DuplicateArrayItem),
+                )?;
+            } else {
+                same_hash.push(unique.len());
+                unique.push(value);
+            }
+        }
+
+        Ok((i, unique))
     }
 }
 
-fn key_value(i: Span<'_>) -> Result<(String, SpannedValue)> {
+fn key_value<K: ObjectKey>(i: Span) -> Result<(K, GenericSpannedValue<K>)> {
     let (i, comma) = opt(char(','))(i)?;
 
     let pos_before_space = Position::from(i);
 
-    let (i, _) = multispace0(i)?;
+    let (i, _) = whitespaces(i)?;
 
     if (i.starts_with('}') || i.is_empty()) && comma.is_none() {
         // Key value is called in a loop, and only an error can stop it
         return Err(Err::Error(Error::default()));
     }
 
-    let (i, key) = preceded(char('"'), string)(i).or_else(|e| match e {
+    let key_start = Position::from(i);
+
+    let (i, key) = preceded(char('"'), map(string, |key| i.key(key)))(i).or_else(|e| match e {
         Err::Error(mut e) => {
             let (i, key) = take_until_delimiter(i, true)?;
 
@@ -345,140 +759,191 @@ fn key_value(i: Span<'_>) -> Result<(String, SpannedValue)> {
         e => Err(e),
     })?;
 
-    let (i, _) = cut(preceded(multispace0, char(':')))(i).map_err(|e: Err<Error>| match e {
+    let key_span = TextRange {
+        start: key_start,
+        end: Position::from_ahead(i),
+    };
+
+    let (j, _) = whitespaces(i)?;
+
+    let (i, _) = cut(char(':'))(j).map_err(|e: Err<Error>| match e {
         Err::Failure(mut e) => {
+            // Spans the key, up to where the colon was expected
             e.kind = Kind::MissingColon;
-            let pos = Position::from(i);
-            e.start = pos.clone();
-            e.end = pos;
+            e.start = key_span.start.clone();
+            e.end = Position::from(i);
             Err::Failure(e)
         }
         e => e,
     })?;
 
-    let (i, value) = json_value(i)?;
+    let (i, mut value) = json_value(i)?;
+    value.key_span = Some(key_span);
 
     Ok((i, (key, value)))
 }
 
-fn hash(i: Span<'_>) -> Result<HashMap<String, SpannedValue>> {
+fn hash<K: ObjectKey>(i: Span) -> Result<GenericMap<K>> {
     let start = Position::from_ahead(i);
 
-    let result: Result<HashMap<String, SpannedValue>> = terminated(
-        map(
-            separated_list0(
-                preceded(
-                    multispace0,
-                    or_else(
-                        map_parser(char(','), |(i, _): (Span, char)| {
-                            let (j, _) = multispace0(i)?;
-
-                            if j.starts_with('}') {
-                                let position = Position::from_ahead(i);
-                                Err(Err::Failure(Error::new(
-                                    position.clone(),
-                                    position,
-                                    Kind::TrailingComma,
-                                )))
-                            } else {
-                                Ok((i, ','))
-                            }
-                        }),
-                        |e: Err<Error>, i| {
-                            let (i, _) = multispace0(i)?;
-
-                            match e {
-                                Err::Error(mut e) if !i.is_empty() && !i.starts_with('}') => {
-                                    e.kind = Kind::MissingComma;
-                                    e.start = start.clone();
-                                    e.end.col -= 1;
-
-                                    Err(Err::Failure(e))
-                                }
-                                e => Err(e),
-                            }
-                        },
-                    ),
-                ),
-                key_value,
-            ),
-            |tuple_vec| tuple_vec.into_iter().collect(),
-        ),
-        preceded(
-            multispace0,
-            or_else(char('}'), |e: Err<Error>, _| match e {
-                Err::Error(mut e) => {
-                    e.kind = Kind::MissingObjectBracket;
-                    e.start = start.clone();
-                    e.end.col -= 1;
-
-                    Err(Err::Failure(e))
-                }
-                e => Err(e),
-            }),
-        ),
-    )(i);
+    let (i, tuple_vec) = elements(i, &start, '}', Kind::MissingObjectBracket, key_value)?;
+
+    if !i.options.error_on_duplicate_keys {
+        return Ok((i, tuple_vec.into_iter().collect()));
+    }
+
+    let mut map = GenericMap::default();
 
-    #[allow(clippy::let_and_return)]
-    result
+    for (key, value) in tuple_vec {
+        if let Some(first) = map.insert(key.clone(), value) {
+            // The first occurrence is put back, and the second one is reported
+            let span = map
+                .insert(key.clone(), first)
+                .and_then(|duplicate| duplicate.key_span)
+                .unwrap_or_default();
+
+            recover(
+                i,
+                Error::new(span.start, span.end, Kind::DuplicateKey(key.to_string())),
+            )?;
+        }
+    }
+
+    Ok((i, map))
 }
 
-fn json_value(i: Span) -> Result<SpannedValue> {
-    let (i, _) = many0(multispace1)(i)?;
+fn json_value<K: ObjectKey>(i: Span) -> Result<GenericSpannedValue<K>> {
+    let before_space = i;
+
+    let (i, _) = whitespaces(i)?;
 
-    let start = Position::from(i);
+    let start = if i.options.include_leading_whitespace_in_span {
+        if i.location_line() == before_space.location_line() {
+            Position::from(before_space)
+        } else {
+            let skipped = &before_space.fragment()[..i.byte_offset() - before_space.byte_offset()];
+
+            Position {
+                line: i.location_line(),
+                col: 1,
+                byte_offset: before_space.byte_offset() + skipped.rfind('\n').map_or(0, |n| n + 1),
+            }
+        }
+    } else {
+        Position::from(i)
+    };
 
     let (i, first_char) = anychar(i)?;
 
     let (i, value) = match first_char {
-        '{' => map(hash, Value::Object)(i),
-        '[' => map(array, Value::Array)(i),
-        '"' => map(string, Value::String)(i),
-        '-' | '0'..='9' => map(number(first_char), Value::Number)(i),
-        't' => map(parse_true, Value::Bool)(i),
-        'f' => map(parse_false, Value::Bool)(i),
-        'n' => map(null, |_| Value::Null)(i),
-        c => {
-            let (i, v) = take_until_delimiter(i, false)?;
-
-            let mut value = String::from(c);
-            value.push_str(&v);
-            drop(v);
-
-            Err(Err::Failure(Error::new(
-                start.clone(),
-                Position::from_ahead(i),
-                Kind::InvalidValue(value),
-            )))
-        }
+        'N' if i.options.allow_non_finite_floats => map(nan, GenericValue::Number)(i),
+        'I' if i.options.allow_non_finite_floats => map(infinity, GenericValue::Number)(i),
+        c if c.is_alphabetic() && i.options.allow_bare_word_strings => bare_word(c, i),
+        '{' => map(hash, GenericValue::Object)(i),
+        '[' => map(array, GenericValue::Array)(i),
+        '"' => map(string, |string| GenericValue::String(string.into_owned()))(i),
+        // `+` and `.` can't start a number, they are parsed to report an invalid number
+        '-' | '+' | '.' | '0'..='9' => map(number(first_char), GenericValue::Number)(i),
+        't' => map(parse_true, GenericValue::Bool)(i),
+        'f' => map(parse_false, GenericValue::Bool)(i),
+        'n' => map(null, |_| GenericValue::Null)(i),
+        c => invalid_value(c, i),
     }?;
 
     let end = Position::from_ahead(i);
 
-    Ok((i, SpannedValue { start, end, value }))
+    Ok((
+        i,
+        GenericSpannedValue {
+            start,
+            end,
+            value,
+            key_span: None,
+        },
+    ))
+}
+
+/// Skips the first token of the chars after the root: a whole value when they start with one,
+/// or the chars up to the next delimiter. Returns true in the first case
+pub(crate) fn first_token(i: Span) -> (Span, bool) {
+    if let Ok((rest, _)) = json_value::<String>(i) {
+        return (rest, true);
+    }
+
+    let len = match i
+        .fragment()
+        .find(|c: char| c.is_whitespace() || ",]}".contains(c))
+    {
+        // The token starts with a delimiter, it's reported alone
+        Some(0) => i.fragment().chars().next().map_or(0, char::len_utf8),
+        Some(len) => len,
+        None => i.fragment().len(),
+    };
+
+    (i.slice(len..), false)
 }
 
-pub fn end_chars(i: Span) -> std::result::Result<(Span, ()), Error> {
-    let (rest, _) = unwrap_nom_error(many0(multispace1)(i))?;
+pub fn end_chars<'a, K: ObjectKey>(
+    i: Span<'a>,
+    root: &GenericSpannedValue<K>,
+) -> std::result::Result<(Span<'a>, ()), Error> {
+    let (mut rest, _) = unwrap_nom_error(whitespaces(i))?;
+
+    // The trailing chars are only inspected, their errors must not be reported
+    rest.recovery = None;
 
     if rest.fragment() == "" {
         return Ok((rest, ()));
     }
 
-    let start = Position::from(rest);
-
-    let (end, _) = unwrap_nom_error(many_till(anychar, eof)(rest))?;
+    let (end, parseable) = first_token(rest);
+
+    // When the trailing chars are valid values, the user probably forgot to wrap them in an array.
+    // The first value has already been parsed by `first_token`, only the next ones are left
+    if parseable {
+        match terminated(many0(json_value::<String>), whitespaces)(end) {
+            Ok((remaining, _)) if remaining.is_empty() => {
+                return Err(Error::new(
+                    Position::from(rest),
+                    Position::from_ahead(end),
+                    Kind::MultipleRootValues {
+                        root_start: root.start.clone(),
+                        root_end: root.end.clone(),
+                    },
+                ));
+            }
+            _ => (),
+        }
+    }
 
     Err(Error::new(
-        start,
+        Position::from(rest),
         Position::from_ahead(end),
-        Kind::CharsAfterRoot(format!(
-            "Unexpected chararacters at the end: {}",
-            rest.fragment()
-        )),
+        Kind::CharsAfterRoot {
+            token: rest.fragment()[..end.byte_offset() - rest.byte_offset()].to_string(),
+            trailing: rest.fragment().to_string(),
+            parseable,
+        },
     ))
 }
 
+/// Creates the input of a whole document. A leading UTF-8 BOM, added by some Windows tools, is
+/// skipped: like in the editors, it doesn't take a column, but it's counted in the byte offsets
+fn document<'a>(s: &'a str, options: &'a ParseOptions) -> Span<'a> {
+    match bom_len(s) {
+        0 => Span::new(s, options),
+        len => Span::at(
+            &s[len..],
+            &Position {
+                line: 1,
+                col: 1,
+                byte_offset: len,
+            },
+            options,
+        ),
+    }
+}
+
 pub fn unwrap_nom_error<T>(value: Result<T>) -> std::result::Result<(Span, T), Error> {
     match value {
         Ok(v) => Ok(v),
@@ -502,11 +967,360 @@ pub fn unwrap_nom_error<T>(value: Result<T>) -> std::result::Result<(Span, T), E
 /// }
 /// ```
 pub fn parse(s: &str) -> ParseResult {
-    let span = Span::new(s);
+    parse_with_options(s, &DEFAULT_OPTIONS)
+}
 
-    let (i, value) = unwrap_nom_error(json_value(span))?;
+/// Same as [parse], but the parsing can be customized with [ParseOptions]
+/// ```ignore
+/// use spanned_json_parse::{parse_with_options, ParseOptions, ValueType};
+///
+/// fn main() {
+///     let options = ParseOptions {
+///         allowed_roots: Some(vec![ValueType::Object]),
+///         ..Default::default()
+///     };
+///
+///     let parsed = parse_with_options("[]", &options);
+///
+///     println!("Parsed: {:#?}", parsed);
+/// }
+/// ```
+pub fn parse_with_options(s: &str, options: &ParseOptions) -> ParseResult {
+    parse_with_warnings(s, options).map(|(value, _)| value)
+}
 
-    let _ = end_chars(i)?;
+/// Parses the first value of `s` and returns it with the byte offset where it ends. Unlike
+/// [parse], the chars after the value are not checked, so it can read concatenated values
+/// (ex: NDJSON) or a value embedded in another format
+/// ```ignore
+/// use spanned_json_parse::parse_prefix;
+///
+/// fn main() {
+///     let (value, offset) = parse_prefix(r#"{"a": 1} rest"#).unwrap();
+///
+///     assert_eq!(offset, 8);
+///     println!("Parsed: {:#?}", value);
+/// }
+/// ```
+pub fn parse_prefix(s: &str) -> std::result::Result<(SpannedValue, usize), Error> {
+    let (i, value) = unwrap_nom_error(json_value(document(s, &DEFAULT_OPTIONS)))?;
+
+    #[cfg(feature = "debug_spans")]
+    value.assert_spans_valid();
+
+    Ok((value, i.byte_offset()))
+}
+
+/// Parses each line of `s` as a separate value, like in [NDJSON](https://github.com/ndjson/ndjson-spec)
+/// or JSON Lines. The positions are relative to the whole input, and the blank lines are skipped.
+/// An invalid line yields an error, and the iteration goes on with the next one
+/// ```ignore
+/// use spanned_json_parse::parse_lines;
+///
+/// fn main() {
+///     let logs = "{\"level\": \"info\"}\n\n{\"level\": \"error\"}\n";
+///
+///     for value in parse_lines(logs) {
+///         let value = value.unwrap();
+///
+///         println!("Line {}: {}", value.start.line, value);
+///     }
+/// }
+/// ```
+pub fn parse_lines(s: &str) -> impl Iterator<Item = ParseResult> + '_ {
+    let mut byte_offset = 0;
+
+    s.split('\n').enumerate().filter_map(move |(index, line)| {
+        let start = Position {
+            line: index + 1,
+            col: 1,
+            byte_offset,
+        };
+
+        byte_offset += line.len() + 1;
+
+        if line.bytes().all(|b| matches!(b, b' ' | b'\t' | b'\r')) {
+            return None;
+        }
+
+        Some(parse_line(line, &start))
+    })
+}
+
+fn parse_line(line: &str, start: &Position) -> ParseResult {
+    let (i, value) = unwrap_nom_error(json_value(Span::at(line, start, &DEFAULT_OPTIONS)))?;
+
+    let _ = end_chars(i, &value)?;
+
+    #[cfg(feature = "debug_spans")]
+    value.assert_spans_valid();
 
     Ok(value)
 }
+
+/// Same as [parse_with_options], but the problems repaired by the parser are returned as
+/// warnings along with the value. For now, these are the containers closed at the end of the
+/// input by [ParseOptions::auto_close_brackets]
+/// ```ignore
+/// use spanned_json_parse::{parse_with_warnings, ParseOptions};
+///
+/// fn main() {
+///     let options = ParseOptions {
+///         auto_close_brackets: true,
+///         ..Default::default()
+///     };
+///
+///     let (value, warnings) = parse_with_warnings(r#"{"a": [1, 2"#, &options).unwrap();
+///
+///     println!("Parsed: {:#?}", value);
+///     println!("Warnings: {:#?}", warnings);
+/// }
+/// ```
+pub fn parse_with_warnings(
+    s: &str,
+    options: &ParseOptions,
+) -> std::result::Result<(SpannedValue, Vec<Error>), Error> {
+    parse_document(s, options, None)
+}
+
+/// Same as [parse], but the equal keys of the objects share the same allocation instead of each
+/// being allocated in its own `String`. It saves memory and time on documents repeating the same
+/// keys many times (ex: an array of objects), the keys of the returned value are an `Arc<str>`.
+/// The returned [InternedSpannedValue] has the same fields and methods as a [SpannedValue]
+/// ```ignore
+/// use spanned_json_parse::parse_interned;
+///
+/// fn main() {
+///     let parsed = parse_interned(r#"[{"id": 1}, {"id": 2}]"#).unwrap();
+///
+///     println!("Parsed: {:#?}", parsed);
+/// }
+/// ```
+pub fn parse_interned(s: &str) -> std::result::Result<InternedSpannedValue, Error> {
+    let keys = Interner::default();
+
+    parse_document(s, &DEFAULT_OPTIONS, Some(&keys)).map(|(value, _)| value)
+}
+
+fn parse_document<K: ObjectKey>(
+    s: &str,
+    options: &ParseOptions,
+    keys: Option<&Interner>,
+) -> std::result::Result<(GenericSpannedValue<K>, Vec<Error>), Error> {
+    check_size(s, options)?;
+
+    let auto_close = AutoClose::default();
+
+    let mut span = document(s, options);
+    span.keys = keys;
+
+    if options.auto_close_brackets {
+        span.recovery = Some(&auto_close);
+    }
+
+    let (i, value) = unwrap_nom_error(json_value(span))?;
+
+    let _ = end_chars(i, &value)?;
+
+    check_root_type(&value, options)?;
+
+    #[cfg(feature = "debug_spans")]
+    value.assert_spans_valid();
+
+    Ok((value, auto_close.warnings.into_inner()))
+}
+
+fn check_size(s: &str, options: &ParseOptions) -> std::result::Result<(), Error> {
+    match options.max_total_bytes {
+        Some(max) if s.len() > max => Err(Error::new(
+            DOCUMENT_START,
+            DOCUMENT_START,
+            Kind::InputTooLarge { size: s.len(), max },
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn check_root_type<K: ObjectKey>(
+    value: &GenericSpannedValue<K>,
+    options: &ParseOptions,
+) -> std::result::Result<(), Error> {
+    if let Some(allowed_roots) = &options.allowed_roots {
+        let found = value.value.value_type();
+
+        if !allowed_roots.contains(&found) {
+            return Err(Error::new(
+                value.start.clone(),
+                value.end.clone(),
+                Kind::DisallowedRootType { found },
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Same as [parse], but the parsing doesn't stop at the first error. The invalid elements of
+/// arrays and objects are skipped, missing commas and brackets are considered as present,
+/// and every error found along the way is collected.
+///
+/// The returned value contains everything that could be parsed, it's `None` only when
+/// the root value itself is invalid
+/// ```ignore
+/// use spanned_json_parse::parse_all;
+///
+/// fn main() {
+///     let (value, errors) = parse_all(r#"[1 2, tru, 3"#);
+///
+///     println!("Parsed: {:#?}", value);
+///     println!("Errors: {:#?}", errors);
+/// }
+/// ```
+pub fn parse_all(s: &str) -> (Option<SpannedValue>, Vec<Error>) {
+    parse_all_with_options(s, &DEFAULT_OPTIONS, |_| Recovery::Skip)
+}
+
+/// Same as [parse_all], but the parsing can be customized with [ParseOptions], and `on_error` is
+/// called with each error to decide whether the parsing goes on or stops
+/// ```ignore
+/// use spanned_json_parse::{parse_all_with_options, ParseOptions, Recovery};
+///
+/// fn main() {
+///     let mut count = 0;
+///
+///     let (value, errors) = parse_all_with_options("[1 2 3 4]", &ParseOptions::default(), |_| {
+///         count += 1;
+///
+///         if count < 50 {
+///             Recovery::Skip
+///         } else {
+///             Recovery::Abort
+///         }
+///     });
+///
+///     println!("Errors: {:#?}", errors);
+/// }
+/// ```
+pub fn parse_all_with_options(
+    s: &str,
+    options: &ParseOptions,
+    on_error: impl FnMut(&Error) -> Recovery,
+) -> (Option<SpannedValue>, Vec<Error>) {
+    if let Err(e) = check_size(s, options) {
+        return (None, vec![e]);
+    }
+
+    let collector = Collector::new(on_error);
+
+    let mut span = document(s, options);
+    span.recovery = Some(&collector);
+
+    let value = match unwrap_nom_error(json_value(span)) {
+        Ok((i, value)) => {
+            if let Err(e) = end_chars(i, &value).and_then(|_| check_root_type(&value, options)) {
+                collector.recover(&e);
+            }
+
+            Some(value)
+        }
+        Err(e) => {
+            // Already collected if it comes from an abort
+            collector.recover(&e);
+
+            None
+        }
+    };
+
+    (value, collector.errors.into_inner())
+}
+
+/// Detects the UTF-16 and UTF-32 encodings, with their BOM or with the null bytes
+/// surrounding the first char, which is always ASCII in a json
+fn detect_encoding(bytes: &[u8]) -> Option<Encoding> {
+    match bytes {
+        [0xFF, 0xFE, 0, 0, ..] | [_, 0, 0, 0, ..] => Some(Encoding::Utf32Le),
+        [0, 0, 0xFE, 0xFF, ..] | [0, 0, 0, _, ..] => Some(Encoding::Utf32Be),
+        [0xFF, 0xFE, ..] | [_, 0, _, 0, ..] => Some(Encoding::Utf16Le),
+        [0xFE, 0xFF, ..] | [0, _, 0, _, ..] => Some(Encoding::Utf16Be),
+        _ => None,
+    }
+}
+
+/// Same as [parse], but from bytes that must be encoded in UTF-8. A [Kind::WrongEncoding] error
+/// is returned when they look like UTF-16 or UTF-32, which happens with files written on Windows
+/// ```ignore
+/// use spanned_json_parse::parse_bytes;
+/// use std::fs;
+///
+/// fn main() {
+///     let json = fs::read("path").unwrap();
+///
+///     let parsed = parse_bytes(&json);
+///
+///     println!("Parsed: {:#?}", parsed);
+/// }
+/// ```
+pub fn parse_bytes(bytes: &[u8]) -> ParseResult {
+    if let Some(detected) = detect_encoding(bytes) {
+        return Err(Error::new(
+            DOCUMENT_START,
+            DOCUMENT_START,
+            Kind::WrongEncoding { detected },
+        ));
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(s) => parse(s),
+        Err(e) => {
+            // The valid part is used to find the position of the first invalid byte
+            let valid = std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default();
+            let span = Span::new(valid, &DEFAULT_OPTIONS);
+            let position = Position::from(span.slice(valid.len()..));
+
+            Err(Error::new(position.clone(), position, Kind::InvalidUtf8))
+        }
+    }
+}
+
+/// Returns the type of the root value by only looking at its first char, for when the type is
+/// needed without paying for a full parse. The value itself isn't validated, so `{]` is an object
+/// ```ignore
+/// use spanned_json_parse::{peek_root_type, ValueType};
+///
+/// fn main() {
+///     let root_type = peek_root_type(r#"  {"hello": "world"}"#);
+///
+///     assert_eq!(root_type.unwrap(), ValueType::Object);
+/// }
+/// ```
+pub fn peek_root_type(s: &str) -> std::result::Result<ValueType, Error> {
+    let span = document(s, &DEFAULT_OPTIONS);
+
+    let (i, _) = unwrap_nom_error(multispace0(span))?;
+    let (i, first_char) = unwrap_nom_error(anychar(i))?;
+
+    match first_char {
+        '{' => Ok(ValueType::Object),
+        '[' => Ok(ValueType::Array),
+        '"' => Ok(ValueType::String),
+        '-' | '0'..='9' => Ok(ValueType::Number),
+        't' | 'f' => Ok(ValueType::Bool),
+        'n' => Ok(ValueType::Null),
+        c => unwrap_nom_error(invalid_value(c, i)).map(|(_, value_type)| value_type),
+    }
+}
+
+/// Parses a single value from `s`, considering that its first char is located at `start`.
+/// Trailing chars are not checked, so `s` can be the tail of a bigger document, and
+/// the unparsed rest of `s` is returned alongside the value
+pub(crate) fn parse_value_at<'a>(
+    s: &'a str,
+    start: &Position,
+    options: &'a ParseOptions,
+) -> std::result::Result<(SpannedValue, &'a str), Error> {
+    let span = Span::at(s, start, options);
+
+    let (i, value) = unwrap_nom_error(json_value(span))?;
+
+    Ok((value, i.fragment()))
+}
@@ -0,0 +1,132 @@
+use crate::value::{Number, SpannedValue, Value};
+use std::fmt::Write;
+
+/// Writes `s` as a json string, with the chars escaped as required by RFC 8259
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\x08' => out.push_str("\\b"),
+            '\x0C' => out.push_str("\\f"),
+            c if c < '\u{20}' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
+
+fn write_number(out: &mut String, number: &Number) {
+    match number {
+        // NaN and the infinities are not valid json
+        Number::Float(num) if !num.is_finite() => out.push_str("null"),
+        // Unlike `Display`, `Debug` keeps the `.0` of the round floats, so they are still floats
+        // when parsed back
+        Number::Float(num) => {
+            let _ = write!(out, "{:?}", num);
+        }
+        number => {
+            let _ = write!(out, "{}", number);
+        }
+    }
+}
+
+struct Printer {
+    out: String,
+    // `None` prints everything on a single line
+    indent: Option<usize>,
+}
+
+impl Printer {
+    fn new_line(&mut self, depth: usize) {
+        if let Some(indent) = self.indent {
+            self.out.push('\n');
+            self.out.push_str(&" ".repeat(indent * depth));
+        }
+    }
+
+    fn value(&mut self, value: &Value, depth: usize) {
+        match value {
+            Value::Null => self.out.push_str("null"),
+            Value::Bool(bool) => self.out.push_str(if *bool { "true" } else { "false" }),
+            Value::Number(number) => write_number(&mut self.out, number),
+            Value::String(str) => write_string(&mut self.out, str),
+            Value::Array(array) if array.is_empty() => self.out.push_str("[]"),
+            Value::Array(array) => {
+                self.out.push('[');
+
+                for (i, element) in array.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    self.new_line(depth + 1);
+                    self.value(&element.value, depth + 1);
+                }
+
+                self.new_line(depth);
+                self.out.push(']');
+            }
+            Value::Object(obj) if obj.is_empty() => self.out.push_str("{}"),
+            Value::Object(_) => {
+                self.out.push('{');
+
+                for (i, (key, element)) in value.object_pairs_sorted().into_iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    self.new_line(depth + 1);
+                    write_string(&mut self.out, key);
+                    self.out.push(':');
+                    if self.indent.is_some() {
+                        self.out.push(' ');
+                    }
+                    self.value(&element.value, depth + 1);
+                }
+
+                self.new_line(depth);
+                self.out.push('}');
+            }
+        }
+    }
+}
+
+fn print(value: &SpannedValue, indent: Option<usize>) -> String {
+    let mut printer = Printer {
+        out: String::new(),
+        indent,
+    };
+
+    printer.value(&value.value, 0);
+
+    printer.out
+}
+
+/// Formats the value as compact json, without going through serde. The keys of the objects are
+/// written in the order they appear in the source, and [Number::RawFloat] and [Number::Raw]
+/// with their text. NaN and the infinities, which can't be written in json, become `null`
+/// ```ignore
+/// use spanned_json_parser::{parse, to_string};
+///
+/// fn main() {
+///     let parsed = parse(r#"{"b": [1, 2], "a": "\n"}"#).unwrap();
+///
+///     assert_eq!(to_string(&parsed), r#"{"b":[1,2],"a":"\n"}"#);
+/// }
+/// ```
+pub fn to_string(value: &SpannedValue) -> String {
+    print(value, None)
+}
+
+/// Same as [to_string], but each element of the arrays and objects is written on its own line,
+/// indented by `indent` spaces per level of nesting
+pub fn to_string_pretty(value: &SpannedValue, indent: usize) -> String {
+    print(value, Some(indent))
+}
@@ -0,0 +1,82 @@
+use crate::error::Error;
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Debug};
+
+/// Returned by the callback of [parse_all_with_options](crate::parse_all_with_options)
+/// to decide what to do after an error
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Recovery {
+    /// Skips the invalid part of the input and keeps parsing
+    Skip,
+    /// Stops the parsing, the errors collected so far are returned
+    Abort,
+}
+
+/// Receives the errors found at each recovery point of the parser
+pub(crate) trait Recover {
+    /// Returns `true` when the parser is allowed to skip the error and continue
+    fn recover(&self, error: &Error) -> bool;
+
+    /// Returns `true` when the container left open at the end of the input can be closed,
+    /// the error is then a warning
+    fn auto_close(&self, _error: &Error) -> bool {
+        false
+    }
+}
+
+/// Rejects every error, but closes the containers left open at the end of the input and keeps
+/// the warnings, used by [ParseOptions::auto_close_brackets](crate::ParseOptions::auto_close_brackets)
+#[derive(Default)]
+pub(crate) struct AutoClose {
+    pub warnings: RefCell<Vec<Error>>,
+}
+
+impl Recover for AutoClose {
+    fn recover(&self, _error: &Error) -> bool {
+        false
+    }
+
+    fn auto_close(&self, error: &Error) -> bool {
+        self.warnings.borrow_mut().push(error.clone());
+
+        true
+    }
+}
+
+pub(crate) struct Collector<F> {
+    pub errors: RefCell<Vec<Error>>,
+    on_error: RefCell<F>,
+    aborted: Cell<bool>,
+}
+
+impl<F: FnMut(&Error) -> Recovery> Collector<F> {
+    pub fn new(on_error: F) -> Self {
+        Self {
+            errors: RefCell::new(Vec::new()),
+            on_error: RefCell::new(on_error),
+            aborted: Cell::new(false),
+        }
+    }
+}
+
+impl<F: FnMut(&Error) -> Recovery> Recover for Collector<F> {
+    fn recover(&self, error: &Error) -> bool {
+        // Once aborted, the error is bubbling up to the root and has already been collected
+        if self.aborted.get() {
+            return false;
+        }
+
+        self.errors.borrow_mut().push(error.clone());
+
+        let recovery = (self.on_error.borrow_mut())(error);
+        self.aborted.set(recovery == Recovery::Abort);
+
+        !self.aborted.get()
+    }
+}
+
+impl Debug for dyn Recover + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Recover")
+    }
+}
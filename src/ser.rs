@@ -1,11 +1,79 @@
 use serde::{
-    ser::{Serialize, SerializeMap, SerializeSeq},
+    ser::{Serialize, SerializeMap, SerializeSeq, SerializeStruct},
     Serializer,
 };
 
-use crate::value::{Number, SpannedValue, Value};
+use crate::value::{
+    GenericSpannedValue, GenericValue, Number, ObjectKey, Position, SpannedValue, Value,
+};
+
+/// Wraps a [SpannedValue] so it's serialized with its spans. Each value is serialized as:
+/// ```json
+/// {
+///     "value": ...,
+///     "start": { "line": 1, "col": 1 },
+///     "end": { "line": 1, "col": 1 }
+/// }
+/// ```
+pub struct SpannedView<'a>(pub &'a SpannedValue);
+
+impl Serialize for Position {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Position", 2)?;
+        state.serialize_field("line", &self.line)?;
+        state.serialize_field("col", &self.col)?;
+        state.end()
+    }
+}
+
+struct ValueView<'a>(&'a Value);
+
+impl<'a> Serialize for SpannedView<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("SpannedValue", 3)?;
+        state.serialize_field("value", &ValueView(&self.0.value))?;
+        state.serialize_field("start", &self.0.start)?;
+        state.serialize_field("end", &self.0.end)?;
+        state.end()
+    }
+}
 
-impl Serialize for SpannedValue {
+impl<'a> Serialize for ValueView<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Value::Array(array) => {
+                let mut seq = serializer.serialize_seq(Some(array.len()))?;
+
+                for v in array {
+                    seq.serialize_element(&SpannedView(v))?;
+                }
+
+                seq.end()
+            }
+            Value::Object(obj) => {
+                let mut map = serializer.serialize_map(Some(obj.len()))?;
+
+                for (k, v) in obj {
+                    map.serialize_entry(k, &SpannedView(v))?;
+                }
+
+                map.end()
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+impl<K: ObjectKey> Serialize for GenericSpannedValue<K> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -14,20 +82,34 @@ impl Serialize for SpannedValue {
     }
 }
 
-impl Serialize for Value {
+impl<K: ObjectKey> Serialize for GenericValue<K> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         match self {
             Self::Null => serializer.serialize_unit(),
-            Value::Number(Number::Float(num)) => serializer.serialize_f64(*num),
-            Value::Number(Number::PosInt(num)) => serializer.serialize_u64(*num),
+            Self::Number(Number::Float(num)) => serializer.serialize_f64(*num),
+            // Serde has no way to pass the text of a number, the serializer formats the float.
+            // The text is kept by the `Display` of the number and by `to_string`
+            Self::Number(Number::RawFloat(num, _)) => serializer.serialize_f64(*num),
+            // The numbers that don't fit in a u64 or an i64 are formatted by the serializer like
+            // the floats. Their text is kept by `to_string`
+            Self::Number(Number::Raw(text)) => match text.parse::<u64>() {
+                Ok(num) => serializer.serialize_u64(num),
+                Err(_) => match text.parse::<i64>() {
+                    Ok(num) => serializer.serialize_i64(num),
+                    Err(_) => {
+                        serializer.serialize_f64(text.parse().map_err(serde::ser::Error::custom)?)
+                    }
+                },
+            },
+            Self::Number(Number::PosInt(num)) => serializer.serialize_u64(*num),
 
-            Value::Number(Number::NegInt(num)) => serializer.serialize_i64(*num),
-            Value::String(str) => serializer.serialize_str(str),
-            Value::Bool(bool) => serializer.serialize_bool(*bool),
-            Value::Array(array) => {
+            Self::Number(Number::NegInt(num)) => serializer.serialize_i64(*num),
+            Self::String(str) => serializer.serialize_str(str),
+            Self::Bool(bool) => serializer.serialize_bool(*bool),
+            Self::Array(array) => {
                 let mut seq = serializer.serialize_seq(Some(array.len()))?;
 
                 for v in array {
@@ -36,11 +118,11 @@ impl Serialize for Value {
 
                 seq.end()
             }
-            Value::Object(obj) => {
+            Self::Object(obj) => {
                 let mut map = serializer.serialize_map(Some(obj.len()))?;
 
                 for (k, v) in obj {
-                    map.serialize_entry(k, v)?;
+                    map.serialize_entry::<str, _>(k.borrow(), v)?;
                 }
 
                 map.end()
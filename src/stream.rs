@@ -0,0 +1,275 @@
+use crate::error::{Error, Kind};
+use crate::options::DEFAULT_OPTIONS;
+use crate::parser::{first_token, parse_value_at, Span};
+use crate::value::{Position, SpannedValue};
+use std::io::{ErrorKind, Read};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+enum State {
+    Start,
+    Elements,
+    End,
+    Done,
+}
+
+/// Iterator over the elements of a root array read from a [Read], returned by [parse_stream]
+pub struct ArrayStream<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    // Index of the first byte of the buffer that hasn't been consumed yet
+    offset: usize,
+    // Position of the byte at `offset` in the document
+    position: Position,
+    // Position of the `[` of the root array
+    opening: Position,
+    state: State,
+}
+
+/// Parses a root array from `reader`, yielding its elements one at a time with the same spans
+/// as [parse](crate::parse). Only the element being parsed is kept in memory, so big documents
+/// (ex: logs) can be processed without reading them into a `String`.
+///
+/// The iteration stops after the first error. The error is located like the one returned by
+/// [parse](crate::parse), but its kind can differ for invalid separators between the elements
+/// ```ignore
+/// use spanned_json_parser::parse_stream;
+/// use std::fs::File;
+///
+/// fn main() {
+///     let file = File::open("path").unwrap();
+///
+///     for element in parse_stream(file) {
+///         let element = element.unwrap();
+///
+///         println!("Element at line {}: {}", element.start.line, element);
+///     }
+/// }
+/// ```
+pub fn parse_stream<R: Read>(reader: R) -> ArrayStream<R> {
+    ArrayStream {
+        reader,
+        buffer: Vec::new(),
+        offset: 0,
+        position: Position {
+            line: 1,
+            col: 1,
+            byte_offset: 0,
+        },
+        opening: Position::default(),
+        state: State::Start,
+    }
+}
+
+impl<R: Read> ArrayStream<R> {
+    fn error(&self, kind: Kind) -> Error {
+        Error::new(self.position.clone(), self.position.clone(), kind)
+    }
+
+    /// Reads the next chunk at the end of the buffer, the consumed bytes are dropped first.
+    /// Returns false when the reader is exhausted
+    fn fill(&mut self) -> Result<bool, Error> {
+        self.buffer.drain(..self.offset);
+        self.offset = 0;
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + CHUNK_SIZE, 0);
+
+        loop {
+            match self.reader.read(&mut self.buffer[len..]) {
+                Ok(read) => {
+                    self.buffer.truncate(len + read);
+                    return Ok(read > 0);
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => {
+                    self.buffer.truncate(len);
+                    return Err(self.error(Kind::Io(e.to_string())));
+                }
+            }
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Error> {
+        while self.offset == self.buffer.len() {
+            if !self.fill()? {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(self.buffer[self.offset]))
+    }
+
+    fn advance(&mut self) {
+        let byte = self.buffer[self.offset];
+        self.offset += 1;
+        self.position.byte_offset += 1;
+
+        if byte == b'\n' {
+            self.position.line += 1;
+            self.position.col = 1;
+        } else if byte & 0xC0 != 0x80 {
+            // Columns are counted in chars, so utf8 continuation bytes are skipped
+            self.position.col += 1;
+        }
+    }
+
+    fn skip_whitespaces(&mut self) -> Result<(), Error> {
+        while matches!(self.peek()?, Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.advance();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the length of the element starting at `offset`, up to the next `,`, `]` or `}`
+    /// that is not nested in a string or a container. The buffer is filled until it's found,
+    /// `None` is returned when the input ends before
+    fn element_len(&mut self) -> Result<Option<usize>, Error> {
+        let mut len = 0;
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        loop {
+            if self.offset + len == self.buffer.len() && !self.fill()? {
+                return Ok(None);
+            }
+
+            let byte = self.buffer[self.offset + len];
+
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' if in_string => escaped = true,
+                b'"' => in_string = !in_string,
+                _ if in_string => (),
+                b',' | b']' | b'}' if depth == 0 => return Ok(Some(len)),
+                b'[' | b'{' => depth += 1,
+                b']' | b'}' => depth -= 1,
+                _ => (),
+            }
+
+            len += 1;
+        }
+    }
+
+    fn start(&mut self) -> Result<(), Error> {
+        self.skip_whitespaces()?;
+
+        if self.peek()? != Some(b'[') {
+            return Err(self.error(Kind::NotAnArray));
+        }
+
+        self.opening = self.position.clone();
+        self.advance();
+        self.skip_whitespaces()?;
+
+        if self.peek()? == Some(b']') {
+            self.advance();
+            self.state = State::End;
+        } else {
+            self.state = State::Elements;
+        }
+
+        Ok(())
+    }
+
+    fn element(&mut self) -> Result<SpannedValue, Error> {
+        self.skip_whitespaces()?;
+
+        let len = match self.element_len()? {
+            Some(0) if self.buffer[self.offset] == b']' => {
+                return Err(self.error(Kind::TrailingComma))
+            }
+            Some(0) => {
+                let found = char::from(self.buffer[self.offset]).to_string();
+                return Err(self.error(Kind::InvalidValue(found)));
+            }
+            Some(len) => len,
+            None => {
+                while self.peek()?.is_some() {
+                    self.advance();
+                }
+
+                return Err(Error::new(
+                    self.opening.clone(),
+                    self.position.clone(),
+                    Kind::MissingArrayBracket,
+                ));
+            }
+        };
+
+        let text = std::str::from_utf8(&self.buffer[self.offset..self.offset + len])
+            .map_err(|_| self.error(Kind::InvalidUtf8))?;
+
+        let (value, rest) = parse_value_at(text, &self.position, &DEFAULT_OPTIONS)?;
+
+        for _ in 0..len - rest.len() {
+            self.advance();
+        }
+
+        self.skip_whitespaces()?;
+
+        match self.peek()? {
+            Some(b',') => self.advance(),
+            Some(b']') => {
+                self.advance();
+                self.state = State::End;
+            }
+            _ => return Err(self.error(Kind::MissingComma)),
+        }
+
+        Ok(value)
+    }
+
+    fn end(&mut self) -> Result<(), Error> {
+        self.skip_whitespaces()?;
+        self.state = State::Done;
+
+        if self.peek()?.is_none() {
+            return Ok(());
+        }
+
+        // Only the chars already read are reported, the rest of the input may be huge
+        let trailing = String::from_utf8_lossy(&self.buffer[self.offset..]).into_owned();
+        let (end, parseable) = first_token(Span::at(&trailing, &self.position, &DEFAULT_OPTIONS));
+        let token = trailing[..end.byte_offset() - self.position.byte_offset].to_string();
+
+        Err(Error::new(
+            self.position.clone(),
+            Position::from_ahead(end),
+            Kind::CharsAfterRoot {
+                token,
+                trailing,
+                parseable,
+            },
+        ))
+    }
+}
+
+impl<R: Read> Iterator for ArrayStream<R> {
+    type Item = Result<SpannedValue, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = loop {
+            match self.state {
+                State::Start => match self.start() {
+                    Ok(()) => (),
+                    Err(e) => break Err(e),
+                },
+                State::Elements => break self.element(),
+                State::End => match self.end() {
+                    Ok(()) => return None,
+                    Err(e) => break Err(e),
+                },
+                State::Done => return None,
+            }
+        };
+
+        if result.is_err() {
+            self.state = State::Done;
+        }
+
+        Some(result)
+    }
+}
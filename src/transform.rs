@@ -0,0 +1,85 @@
+use crate::parser::{parse, ParseResult};
+use crate::value::{SpannedValue, Value};
+
+/// Rewrites a string value, see [parse_with_transforms]
+pub type Transform = Box<dyn Fn(&str) -> String>;
+
+/// A [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) where a `*` token
+/// matches any key or index (ex: `/paths/*`)
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pattern {
+    tokens: Vec<String>,
+}
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Self {
+        let tokens = pattern
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect();
+
+        Self { tokens }
+    }
+
+    fn matches(&self, path: &[String]) -> bool {
+        self.tokens.len() == path.len()
+            && self
+                .tokens
+                .iter()
+                .zip(path)
+                .all(|(token, segment)| token == "*" || token == segment)
+    }
+}
+
+fn apply(value: &mut SpannedValue, transforms: &[(Pattern, Transform)], path: &mut Vec<String>) {
+    match &mut value.value {
+        Value::String(s) => {
+            for (pattern, transform) in transforms {
+                if pattern.matches(path) {
+                    *s = transform(s);
+                }
+            }
+        }
+        Value::Array(array) => {
+            for (i, v) in array.iter_mut().enumerate() {
+                path.push(i.to_string());
+                apply(v, transforms, path);
+                path.pop();
+            }
+        }
+        Value::Object(obj) => {
+            for (k, v) in obj.iter_mut() {
+                path.push(k.clone());
+                apply(v, transforms, path);
+                path.pop();
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Same as [parse], then the string values whose pointer matches a [Pattern] are rewritten by
+/// its [Transform]. When several patterns match, the transforms are applied in order.
+/// The spans are left untouched, they still point to the original strings
+/// ```ignore
+/// use spanned_json_parser::{parse_with_transforms, Pattern, Transform};
+///
+/// fn main() {
+///     let transforms: Vec<(Pattern, Transform)> = vec![(
+///         Pattern::new("/paths/*"),
+///         Box::new(|s| s.replace("$HOME", "/home/user")),
+///     )];
+///
+///     let parsed = parse_with_transforms(r#"{"paths": ["$HOME/.config"]}"#, &transforms);
+///
+///     println!("Parsed: {:#?}", parsed);
+/// }
+/// ```
+pub fn parse_with_transforms(s: &str, transforms: &[(Pattern, Transform)]) -> ParseResult {
+    let mut value = parse(s)?;
+
+    apply(&mut value, transforms, &mut Vec::new());
+
+    Ok(value)
+}
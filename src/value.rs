@@ -1,5 +1,15 @@
+use crate::date::is_iso8601;
+use crate::diagnostic::LineIndex;
+#[cfg(feature = "base64")]
+use crate::error::{Error, Kind};
 use crate::input::Input;
-use std::{collections::HashMap, fmt::Display};
+use crate::intern::Sealed;
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
@@ -8,6 +18,51 @@ pub enum Number {
     PosInt(u64),
     NegInt(i64),
     Float(f64),
+    /// A float along with its text in the source, kept when
+    /// [ParseOptions::preserve_float_text](crate::ParseOptions::preserve_float_text) is set
+    RawFloat(f64, String),
+    /// The text of a number that can't be stored in the other variants without losing precision
+    /// (ex: `12345678901234567890123`), kept when
+    /// [ParseOptions::arbitrary_precision](crate::ParseOptions::arbitrary_precision) is set
+    Raw(String),
+}
+
+impl Number {
+    /// Returns the number as a float, which loses precision for the integers above 2^53
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Self::PosInt(num) => *num as f64,
+            Self::NegInt(num) => *num as f64,
+            Self::Float(num) | Self::RawFloat(num, _) => *num,
+            Self::Raw(text) => text.parse().unwrap_or(f64::NAN),
+        }
+    }
+
+    /// Returns the number if it's an integer that fits in an `i64`
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::PosInt(num) => i64::try_from(*num).ok(),
+            Self::NegInt(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if it's a positive integer that fits in an `u64`
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::PosInt(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    /// Checks that the number has been written without decimals nor exponent
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Self::PosInt(_) | Self::NegInt(_) => true,
+            Self::Float(_) | Self::RawFloat(..) => false,
+            Self::Raw(text) => !text.contains(['.', 'e', 'E']),
+        }
+    }
 }
 
 impl Display for Number {
@@ -15,22 +70,110 @@ impl Display for Number {
         match self {
             Self::PosInt(num) => write!(f, "{}", num),
             Self::NegInt(num) => write!(f, "{}", num),
+            Self::Float(num) if num.is_nan() => write!(f, "NaN"),
+            Self::Float(num) if num.is_infinite() && *num > 0. => write!(f, "Infinity"),
+            Self::Float(num) if num.is_infinite() => write!(f, "-Infinity"),
             Self::Float(num) => write!(f, "{}", num),
+            Self::RawFloat(_, text) | Self::Raw(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Returns the bits of a float, with `-0.0` hashed like `0.0` as they are equal
+fn float_bits(num: f64) -> u64 {
+    if num == 0. {
+        0
+    } else {
+        num.to_bits()
+    }
+}
+
+/// The floats are hashed by their bit pattern. `-0.0` is hashed like `0.0` so equal numbers have
+/// the same hash, and the NaNs, which are never equal, are hashed as they are
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            Self::PosInt(num) => num.hash(state),
+            Self::NegInt(num) => num.hash(state),
+            Self::Float(num) => float_bits(*num).hash(state),
+            Self::RawFloat(num, text) => {
+                float_bits(*num).hash(state);
+                text.hash(state);
+            }
+            Self::Raw(text) => text.hash(state),
         }
     }
 }
 
+/// The map storing the entries of an object. With the `preserve_order` feature, it's an
+/// [IndexMap](indexmap::IndexMap) keeping the keys in the order they appear in the source
+#[cfg(not(feature = "preserve_order"))]
+pub type GenericMap<K> = HashMap<K, GenericSpannedValue<K>>;
+#[cfg(feature = "preserve_order")]
+pub type GenericMap<K> = indexmap::IndexMap<K, GenericSpannedValue<K>>;
+
+pub type Map = GenericMap<String>;
+pub type InternedMap = GenericMap<Arc<str>>;
+
+/// The type of the keys of a [GenericMap]: a `String`, or an `Arc<str>` shared by the equal keys
+/// of a document parsed by [parse_interned](crate::parse_interned)
+pub trait ObjectKey: Eq + Hash + Clone + Debug + Display + Borrow<str> + Sealed {}
+
+impl ObjectKey for String {}
+impl ObjectKey for Arc<str> {}
+
+/// A json value, `K` being the type of its object keys. It's used through [Value], or
+/// [InternedValue] for the values returned by [parse_interned](crate::parse_interned)
 #[derive(Debug, PartialEq, Clone)]
-pub enum Value {
+pub enum GenericValue<K: ObjectKey> {
     Null,
     Number(Number),
+    // The string is always owned, there's no zero-copy mode borrowing it from the source as a
+    // `Cow<'a, str>`: the lifetime would reach every value and the public functions returning
+    // them. The strings without escapes are still borrowed while parsing, then copied at once.
+    // A big string is kept contiguous rather than split into a rope, so it can still be
+    // borrowed as a `&str`. Its chars up to the first escape are copied at once, so it's
+    // allocated with its exact size instead of doubling while it's parsed
     String(String),
     Bool(bool),
-    Array(Vec<SpannedValue>),
-    Object(HashMap<String, SpannedValue>),
+    // Arrays can't store their elements inline (ex: `SmallVec<[SpannedValue; 4]>`), as a
+    // `SpannedValue` would then contain itself and have an infinite size. Boxing the inline
+    // storage brings back the allocation it was meant to avoid, so a `Vec` is used
+    Array(Vec<GenericSpannedValue<K>>),
+    Object(GenericMap<K>),
+}
+
+pub type Value = GenericValue<String>;
+pub type InternedValue = GenericValue<Arc<str>>;
+
+/// The type of a [Value], without its content
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ValueType {
+    Null,
+    Number,
+    String,
+    Bool,
+    Array,
+    Object,
+}
+
+impl ValueType {
+    /// The lowercase name of the type, to write messages like `expected a string, found number`
+    pub fn type_name(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Bool => "bool",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
 }
 
-impl Display for Value {
+impl<K: ObjectKey> Display for GenericValue<K> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Null => write!(f, "()"),
@@ -43,7 +186,142 @@ impl Display for Value {
     }
 }
 
-impl Value {
+/// The spans are not hashed, so the values equal according to [Value::value_eq] have the same
+/// hash. As a `Value` can contain NaN, it can't implement `Eq`: to find duplicates, the values
+/// can be grouped by hash, then compared with [Value::value_eq]
+impl<K: ObjectKey> Hash for GenericValue<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            Self::Null => (),
+            Self::Number(num) => num.hash(state),
+            Self::String(str) => str.hash(state),
+            Self::Bool(bool) => bool.hash(state),
+            Self::Array(array) => {
+                array.len().hash(state);
+                array.iter().for_each(|v| v.value.hash(state));
+            }
+            Self::Object(obj) => {
+                // The entries are hashed separately and summed, so the order of the keys
+                // doesn't change the hash
+                let entries = obj.iter().fold(0u64, |sum, (k, v)| {
+                    let mut hasher = DefaultHasher::new();
+                    k.hash(&mut hasher);
+                    v.value.hash(&mut hasher);
+                    sum.wrapping_add(hasher.finish())
+                });
+
+                obj.len().hash(state);
+                entries.hash(state);
+            }
+        }
+    }
+}
+
+// Implemented whatever the type of the keys, so they also work on the values of
+// [parse_interned](crate::parse_interned)
+impl<K: ObjectKey> GenericValue<K> {
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Self::Null => ValueType::Null,
+            Self::Number(_) => ValueType::Number,
+            Self::String(_) => ValueType::String,
+            Self::Bool(_) => ValueType::Bool,
+            Self::Array(_) => ValueType::Array,
+            Self::Object(_) => ValueType::Object,
+        }
+    }
+
+    /// Compares the values while ignoring their spans, so the same document formatted differently
+    /// is equal. The order of the keys of an object doesn't matter
+    pub fn value_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Array(a), Self::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.value.value_eq(&b.value))
+            }
+            (Self::Object(a), Self::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(k, a)| {
+                        b.get::<str>(k.borrow())
+                            .is_some_and(|b| a.value.value_eq(&b.value))
+                    })
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// The lowercase name of the type, to write messages like `expected a string, found number`
+    pub fn type_name(&self) -> &'static str {
+        self.value_type().type_name()
+    }
+
+    pub fn as_null(&self) -> Option<()> {
+        match self {
+            Self::Null => Some(()),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            Self::String(str) => Some(str),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Self::Number(num) => Some(num),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(bool) => Some(*bool),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<GenericSpannedValue<K>>> {
+        match self {
+            Self::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&GenericMap<K>> {
+        match self {
+            Self::Object(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    /// Consumes the value, returning its string without cloning it
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            Self::String(str) => Some(str),
+            _ => None,
+        }
+    }
+
+    /// Consumes the value, returning its elements without cloning them
+    pub fn into_array(self) -> Option<Vec<GenericSpannedValue<K>>> {
+        match self {
+            Self::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Consumes the value, returning its entries without cloning them
+    pub fn into_object(self) -> Option<GenericMap<K>> {
+        match self {
+            Self::Object(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
     pub fn unwrap_null(&self) {
         match self {
             Self::Null => (),
@@ -72,14 +350,14 @@ impl Value {
         }
     }
 
-    pub fn unwrap_array(&self) -> &Vec<SpannedValue> {
+    pub fn unwrap_array(&self) -> &Vec<GenericSpannedValue<K>> {
         match self {
             Self::Array(array) => array,
             _ => panic!("Try to get array, but value is not a array: {}", self),
         }
     }
 
-    pub fn unwrap_object(&self) -> &HashMap<String, SpannedValue> {
+    pub fn unwrap_object(&self) -> &GenericMap<K> {
         match self {
             Self::Object(obj) => obj,
             _ => panic!("Try to get object, but value is not a object: {}", self),
@@ -87,18 +365,884 @@ impl Value {
     }
 }
 
+impl Value {
+    /// Builds an array from values without spans, ex: to write the expected value of a test
+    /// ```ignore
+    /// use spanned_json_parser::{parse, Value};
+    ///
+    /// fn main() {
+    ///     let expected = Value::object([
+    ///         ("name", Value::from("app")),
+    ///         ("ids", Value::array([1u64, 2])),
+    ///     ]);
+    ///     let parsed = parse(r#"{"ids": [1, 2], "name": "app"}"#).unwrap();
+    ///
+    ///     assert!(parsed.value.value_eq(&expected));
+    /// }
+    /// ```
+    pub fn array<V: Into<Value>>(elements: impl IntoIterator<Item = V>) -> Self {
+        Self::Array(
+            elements
+                .into_iter()
+                .map(|v| SpannedValue::from(v.into()))
+                .collect(),
+        )
+    }
+
+    /// Builds an object from entries without spans, see [Value::array]
+    pub fn object<K: Into<String>, V: Into<Value>>(
+        entries: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.into(), SpannedValue::from(v.into())))
+                .collect(),
+        )
+    }
+
+    /// Returns the types of the elements of an array, or `None` if the value is not an array
+    pub fn array_element_types(&self) -> Option<HashSet<ValueType>> {
+        self.as_array()
+            .map(|array| array.iter().map(|v| v.value.value_type()).collect())
+    }
+
+    /// Returns `true` if the value is an array whose elements all have the same type.
+    /// An empty array is homogeneous
+    pub fn is_homogeneous_array(&self) -> bool {
+        self.array_element_types()
+            .is_some_and(|types| types.len() <= 1)
+    }
+
+    /// Returns the entries of an object in the order they appear in the source, with the span
+    /// of their key. Nothing is returned if the value is not an object
+    pub fn entries(&self) -> impl Iterator<Item = SpannedEntry<'_>> {
+        self.object_pairs_sorted()
+            .into_iter()
+            .map(|(key, value)| SpannedEntry {
+                key,
+                key_span: value.key_span.clone().unwrap_or_default(),
+                value,
+            })
+    }
+
+    /// Returns the entries of an object in the order they appear in the source,
+    /// or an empty vec if the value is not an object
+    pub fn object_pairs_sorted(&self) -> Vec<(&String, &SpannedValue)> {
+        match self {
+            Self::Object(obj) => {
+                let mut entries: Vec<_> = obj.iter().collect();
+                entries.sort_by_key(|(_, v)| (v.start.line, v.start.col));
+                entries
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl IntoIterator for Value {
+    type Item = (PathSegment, SpannedValue);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    /// Consumes the value, yielding the entries of an object, in the order they appear in the
+    /// source, or the elements of an array with their index. Nothing is yielded for the other types
+    fn into_iter(self) -> Self::IntoIter {
+        let items: Vec<_> = match self {
+            Self::Array(array) => array
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (PathSegment::Index(i), v))
+                .collect(),
+            Self::Object(obj) => {
+                let mut entries: Vec<_> = obj.into_iter().collect();
+                entries.sort_by_key(|(_, v)| (v.start.line, v.start.col));
+
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (PathSegment::Key(k), v))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+
+        items.into_iter()
+    }
+}
+
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-#[derive(Debug, PartialEq, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Position {
     pub col: usize,
     pub line: usize,
+    /// The offset, in bytes, of the char in the source, so `&source[start.byte_offset..]`
+    /// starts at the value
+    pub byte_offset: usize,
+}
+
+impl Ord for Position {
+    /// Orders the positions by line, then by column, so the earliest position in the source
+    /// comes first
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.line, self.col, self.byte_offset).cmp(&(other.line, other.col, other.byte_offset))
+    }
+}
+
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The range of chars between two positions, both included
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TextRange {
+    pub start: Position,
+    pub end: Position,
 }
 
+impl TextRange {
+    /// Returns the smallest range covering both ranges, ex: to span two sibling values
+    pub fn merge(&self, other: &Self) -> Self {
+        Self {
+            start: self.start.clone().min(other.start.clone()),
+            end: self.end.clone().max(other.end.clone()),
+        }
+    }
+
+    /// Returns true if `position` is between the start and the end of the range, both included.
+    /// Only the line and the column are compared, so the byte offset of `position` can be unknown
+    pub fn contains(&self, position: &Position) -> bool {
+        let position = (position.line, position.col);
+
+        (self.start.line, self.start.col) <= position && position <= (self.end.line, self.end.col)
+    }
+}
+
+/// A step in the path leading to a nested value
+#[derive(Debug, PartialEq, Clone)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// A value replaced by a value of another type during a merge
 #[derive(Debug, PartialEq, Clone)]
-pub struct SpannedValue {
-    pub value: Value,
+pub struct Conflict {
+    /// The [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) of the value
+    pub pointer: String,
+    pub base: TextRange,
+    pub base_type: ValueType,
+    pub overlay: TextRange,
+    pub overlay_type: ValueType,
+}
+
+/// A json value with its span, `K` being the type of its object keys. It's used through
+/// [SpannedValue], or [InternedSpannedValue] for the values returned by
+/// [parse_interned](crate::parse_interned)
+#[derive(PartialEq, Clone)]
+pub struct GenericSpannedValue<K: ObjectKey> {
+    pub value: GenericValue<K>,
     pub start: Position,
     pub end: Position,
+    /// The span of the key, quotes included, when the value belongs to an object
+    pub key_span: Option<TextRange>,
+}
+
+pub type SpannedValue = GenericSpannedValue<String>;
+pub type InternedSpannedValue = GenericSpannedValue<Arc<str>>;
+
+// Written by hand so the values are printed as a `SpannedValue`, the name used everywhere else
+impl<K: ObjectKey> Debug for GenericSpannedValue<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpannedValue")
+            .field("value", &self.value)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("key_span", &self.key_span)
+            .finish()
+    }
+}
+
+/// An entry of an object, with the span of its key
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpannedEntry<'a> {
+    pub key: &'a str,
+    pub key_span: TextRange,
+    pub value: &'a SpannedValue,
+}
+
+/// Formats a path as a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901)
+fn to_pointer(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) => format!("/{}", escape_pointer_token(key)),
+            PathSegment::Index(i) => format!("/{}", i),
+        })
+        .collect()
+}
+
+/// Escapes a key to be used as a token of a JSON Pointer
+fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Parses an array index of a JSON Pointer, which can't have a sign or leading zeros
+fn array_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+
+    token.parse().ok()
+}
+
+impl SpannedValue {
+    pub fn span(&self) -> TextRange {
+        TextRange {
+            start: self.start.clone(),
+            end: self.end.clone(),
+        }
+    }
+
+    /// Same as [Value::value_eq]: the spans are ignored, so two documents formatted differently
+    /// are equal, whatever the order of the keys of their objects
+    pub fn eq_ignore_spans(&self, other: &Self) -> bool {
+        self.value.value_eq(&other.value)
+    }
+
+    /// Returns the value of `key`, or `None` if the value is not an object or has no such key.
+    /// The object can also be indexed, which panics when the key is missing
+    /// ```ignore
+    /// use spanned_json_parser::parse;
+    ///
+    /// fn main() {
+    ///     let parsed = parse(r#"{"vec": [{"num1": 1}]}"#).unwrap();
+    ///
+    ///     assert!(parsed.get("vec").is_some());
+    ///
+    ///     let num1 = &parsed["vec"][0]["num1"];
+    ///
+    ///     println!("num1 is at line {}, column {}", num1.start.line, num1.start.col);
+    /// }
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&SpannedValue> {
+        self.value.as_object()?.get(key)
+    }
+
+    /// Returns the element at `index`, or `None` if the value is not an array or is too short
+    pub fn get_index(&self, index: usize) -> Option<&SpannedValue> {
+        self.value.as_array()?.get(index)
+    }
+
+    /// Returns the innermost value whose span contains `position`, ex: the value under the cursor
+    /// of an editor. `None` is returned when `position` is outside of the value
+    /// ```ignore
+    /// use spanned_json_parser::{parse, Position};
+    ///
+    /// fn main() {
+    ///     let parsed = parse(r#"{"vec": [1, 22]}"#).unwrap();
+    ///
+    ///     let cursor = Position {
+    ///         line: 1,
+    ///         col: 14,
+    ///         byte_offset: 0,
+    ///     };
+    ///
+    ///     assert_eq!(parsed.value_at(&cursor), Some(&parsed["vec"][1]));
+    /// }
+    /// ```
+    pub fn value_at(&self, position: &Position) -> Option<&SpannedValue> {
+        if !self.span().contains(position) {
+            return None;
+        }
+
+        // The children don't overlap, so at most one of them contains the position
+        let child = match &self.value {
+            Value::Array(array) => array.iter().find_map(|v| v.value_at(position)),
+            Value::Object(obj) => obj.values().find_map(|v| v.value_at(position)),
+            _ => None,
+        };
+
+        Some(child.unwrap_or(self))
+    }
+
+    /// Same as [Value::into_string], the span is dropped
+    pub fn into_string(self) -> Option<String> {
+        self.value.into_string()
+    }
+
+    /// Same as [Value::into_array], the span of the array is dropped
+    pub fn into_array(self) -> Option<Vec<SpannedValue>> {
+        self.value.into_array()
+    }
+
+    /// Same as [Value::into_object], the span of the object is dropped
+    pub fn into_object(self) -> Option<Map> {
+        self.value.into_object()
+    }
+
+    /// Returns the text of `source` the value has been parsed from, ex: to get the original
+    /// spelling of a number. `source` must be the string that has been parsed, an empty string is
+    /// returned when the span doesn't fit in it
+    /// ```ignore
+    /// use spanned_json_parser::parse;
+    ///
+    /// fn main() {
+    ///     let json = r#"{"big": 1.2e12}"#;
+    ///     let parsed = parse(json).unwrap();
+    ///
+    ///     assert_eq!(parsed["big"].source(json), "1.2e12");
+    /// }
+    /// ```
+    pub fn source<'a>(&self, source: &'a str) -> &'a str {
+        // The end is the offset of the last char, which must be included
+        let end = source
+            .get(self.end.byte_offset..)
+            .and_then(|rest| rest.chars().next())
+            .map(|c| self.end.byte_offset + c.len_utf8());
+
+        end.and_then(|end| source.get(self.start.byte_offset..end))
+            .unwrap_or("")
+    }
+
+    /// Returns the number of chars of `source` covered by the span of the value, line breaks
+    /// included. It differs from the length of a string value when it contains escaped chars.
+    /// `source` must be the string that has been parsed
+    pub fn char_len(&self, source: &str) -> usize {
+        let index = LineIndex::new(source);
+        let start = index.position_to_offset(&self.start);
+        let end = index.position_to_offset(&self.end);
+
+        match (start, end) {
+            (Some(start), Some(end)) if start <= end => {
+                source[start..].chars().count() - source[end..].chars().count() + 1
+            }
+            _ => 0,
+        }
+    }
+
+    /// Recursively resets every position to [Position::default] and removes the key spans,
+    /// for when the spans are not meaningful anymore
+    pub fn strip_spans(&mut self) {
+        self.start = Position::default();
+        self.end = Position::default();
+        self.key_span = None;
+
+        match &mut self.value {
+            Value::Array(array) => array.iter_mut().for_each(SpannedValue::strip_spans),
+            Value::Object(obj) => obj.values_mut().for_each(SpannedValue::strip_spans),
+            _ => (),
+        }
+    }
+
+    /// Recursively searches for the first value associated to `key`, in document order
+    pub fn find_key(&self, key: &str) -> Option<&SpannedValue> {
+        match &self.value {
+            Value::Array(array) => array.iter().find_map(|v| v.find_key(key)),
+            Value::Object(_) => self
+                .value
+                .object_pairs_sorted()
+                .into_iter()
+                .find_map(|(k, v)| if k == key { Some(v) } else { v.find_key(key) }),
+            _ => None,
+        }
+    }
+
+    /// Recursively searches for all the values associated to `key`, in document order,
+    /// with the path leading to them
+    pub fn find_all_keys(&self, key: &str) -> Vec<(Vec<PathSegment>, &SpannedValue)> {
+        let mut found = Vec::new();
+        self.find_all_keys_at(key, &mut Vec::new(), &mut found);
+        found
+    }
+
+    fn find_all_keys_at<'a>(
+        &'a self,
+        key: &str,
+        path: &mut Vec<PathSegment>,
+        found: &mut Vec<(Vec<PathSegment>, &'a SpannedValue)>,
+    ) {
+        match &self.value {
+            Value::Array(array) => {
+                for (i, v) in array.iter().enumerate() {
+                    path.push(PathSegment::Index(i));
+                    v.find_all_keys_at(key, path, found);
+                    path.pop();
+                }
+            }
+            Value::Object(_) => {
+                for (k, v) in self.value.object_pairs_sorted() {
+                    path.push(PathSegment::Key(k.clone()));
+                    if k == key {
+                        found.push((path.clone(), v));
+                    }
+                    v.find_all_keys_at(key, path, found);
+                    path.pop();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Follows `keys` through nested objects. Unlike a pointer, the keys don't need to be
+    /// escaped, but array indices aren't supported
+    pub fn deep_get(&self, keys: &[&str]) -> Option<&SpannedValue> {
+        keys.iter()
+            .try_fold(self, |value, key| value.value.as_object()?.get(*key))
+    }
+
+    /// Merges `overlay` into the value, for when configs are composed of several layers.
+    ///
+    /// When both values are objects, their entries are merged recursively: the keys of the value
+    /// keep their order, and the keys only present in `overlay` are appended in their own order.
+    /// Otherwise the value is replaced by `overlay`
+    #[cfg(feature = "preserve_order")]
+    pub fn ordered_merge(&mut self, overlay: &SpannedValue) {
+        self.merge_at(overlay, &mut Vec::new(), &mut Vec::new());
+    }
+
+    /// Merges `overlay` into the value: objects are merged recursively, and any other value is
+    /// replaced by `overlay`. Returns a [Conflict] for each value replaced by a value of another type
+    pub fn merge_with_conflicts(&mut self, overlay: &SpannedValue) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        self.merge_at(overlay, &mut Vec::new(), &mut conflicts);
+        conflicts
+    }
+
+    fn merge_at(
+        &mut self,
+        overlay: &SpannedValue,
+        path: &mut Vec<PathSegment>,
+        conflicts: &mut Vec<Conflict>,
+    ) {
+        match (&mut self.value, &overlay.value) {
+            (Value::Object(base), Value::Object(entries)) => {
+                for (key, value) in entries {
+                    match base.get_mut(key) {
+                        Some(existing) => {
+                            path.push(PathSegment::Key(key.clone()));
+                            existing.merge_at(value, path, conflicts);
+                            path.pop();
+                        }
+                        None => {
+                            base.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+            }
+            _ => {
+                let base_type = self.value.value_type();
+                let overlay_type = overlay.value.value_type();
+
+                if base_type != overlay_type {
+                    conflicts.push(Conflict {
+                        pointer: to_pointer(path),
+                        base: self.span(),
+                        base_type,
+                        overlay: overlay.span(),
+                        overlay_type,
+                    });
+                }
+
+                *self = overlay.clone();
+            }
+        }
+    }
+
+    /// Recursively adds the keys of `defaults` that are missing in the value, existing keys are
+    /// never overwritten. The inserted values keep their span in `defaults`
+    pub fn apply_defaults(&mut self, defaults: &SpannedValue) {
+        if let (Value::Object(obj), Value::Object(defaults)) = (&mut self.value, &defaults.value) {
+            for (key, default) in defaults {
+                match obj.get_mut(key) {
+                    Some(existing) => existing.apply_defaults(default),
+                    None => {
+                        obj.insert(key.clone(), default.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the string values that are ISO-8601 dates or date times (ex: `2023-01-01T00:00:00Z`),
+    /// in document order, with their [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901).
+    /// The values are left as strings, it only tells which ones are dates
+    pub fn date_strings(&self) -> Vec<(String, &SpannedValue)> {
+        let mut found = Vec::new();
+        self.date_strings_at(&mut Vec::new(), &mut found);
+        found
+    }
+
+    fn date_strings_at<'a>(
+        &'a self,
+        path: &mut Vec<PathSegment>,
+        found: &mut Vec<(String, &'a SpannedValue)>,
+    ) {
+        match &self.value {
+            Value::String(s) if is_iso8601(s) => found.push((to_pointer(path), self)),
+            Value::Array(array) => {
+                for (i, v) in array.iter().enumerate() {
+                    path.push(PathSegment::Index(i));
+                    v.date_strings_at(path, found);
+                    path.pop();
+                }
+            }
+            Value::Object(_) => {
+                for (k, v) in self.value.object_pairs_sorted() {
+                    path.push(PathSegment::Key(k.clone()));
+                    v.date_strings_at(path, found);
+                    path.pop();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns the references of the objects made of a single `$ref` string
+    /// (ex: `{"$ref": "#/definitions/Foo"}`), in document order, with the span of the
+    /// reference string so a missing target can be reported on it
+    pub fn refs(&self) -> Vec<(&str, TextRange)> {
+        let mut found = Vec::new();
+        self.refs_at(&mut found);
+        found
+    }
+
+    fn refs_at<'a>(&'a self, found: &mut Vec<(&'a str, TextRange)>) {
+        match &self.value {
+            Value::Array(array) => array.iter().for_each(|v| v.refs_at(found)),
+            Value::Object(obj) => match obj.get("$ref") {
+                Some(SpannedValue {
+                    value: Value::String(reference),
+                    ..
+                }) if obj.len() == 1 => found.push((reference, obj["$ref"].span())),
+                _ => {
+                    for (_, v) in self.value.object_pairs_sorted() {
+                        v.refs_at(found);
+                    }
+                }
+            },
+            _ => (),
+        }
+    }
+
+    /// Replaces every string value by the result of `f`, keys are left untouched.
+    /// The spans are preserved, they still point to the original strings
+    pub fn map_strings<F: FnMut(&str) -> String>(&mut self, mut f: F) {
+        self.map_strings_with(&mut f);
+    }
+
+    fn map_strings_with<F: FnMut(&str) -> String>(&mut self, f: &mut F) {
+        match &mut self.value {
+            Value::String(s) => *s = f(s),
+            Value::Array(array) => array.iter_mut().for_each(|v| v.map_strings_with(f)),
+            Value::Object(obj) => obj.values_mut().for_each(|v| v.map_strings_with(f)),
+            _ => (),
+        }
+    }
+
+    /// Calls `f` on the value and all its descendants, depth-first. `f` is called on an array or
+    /// an object before its elements, so the elements it adds or replaces are visited too.
+    /// The entries of an object are visited in the order of the [Map]
+    /// ```ignore
+    /// use spanned_json_parser::{parse, Value};
+    ///
+    /// fn main() {
+    ///     let mut parsed = parse(r#"{"Name": " app ", "Tags": ["A "]}"#).unwrap();
+    ///
+    ///     parsed.visit_mut(|v| match &mut v.value {
+    ///         Value::String(s) => *s = s.trim().to_string(),
+    ///         Value::Object(obj) => {
+    ///             *obj = std::mem::take(obj)
+    ///                 .into_iter()
+    ///                 .map(|(k, v)| (k.to_lowercase(), v))
+    ///                 .collect();
+    ///         }
+    ///         _ => (),
+    ///     });
+    ///
+    ///     assert_eq!(parsed["tags"][0].value.unwrap_string(), "A");
+    /// }
+    /// ```
+    pub fn visit_mut<F: FnMut(&mut SpannedValue)>(&mut self, mut f: F) {
+        self.visit_mut_with(&mut f);
+    }
+
+    fn visit_mut_with<F: FnMut(&mut SpannedValue)>(&mut self, f: &mut F) {
+        f(self);
+
+        match &mut self.value {
+            Value::Array(array) => array.iter_mut().for_each(|v| v.visit_mut_with(f)),
+            Value::Object(obj) => obj.values_mut().for_each(|v| v.visit_mut_with(f)),
+            _ => (),
+        }
+    }
+
+    /// Resolves a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) like `/vec/0/num1`.
+    /// The addressed value keeps its span, so it can be used to report an error
+    /// ```ignore
+    /// use spanned_json_parser::parse;
+    ///
+    /// fn main() {
+    ///     let parsed = parse(r#"{"vec": [{"num1": 1}]}"#).unwrap();
+    ///
+    ///     let num1 = parsed.pointer("/vec/0/num1").unwrap();
+    ///
+    ///     println!("num1 is at line {}, column {}", num1.start.line, num1.start.col);
+    /// }
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&SpannedValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .try_fold(self, |value, token| match &value.value {
+                Value::Object(obj) => obj.get(&token),
+                Value::Array(array) => array.get(array_index(&token)?),
+                _ => None,
+            })
+    }
+
+    /// Returns the elements of the array located at `pointer`, if they are all numbers
+    fn numbers_at(&self, pointer: &str) -> Option<Vec<f64>> {
+        self.pointer(pointer)?
+            .value
+            .as_array()?
+            .iter()
+            .map(|v| v.value.as_number().map(Number::as_f64))
+            .collect()
+    }
+
+    /// Sums the numbers of the array located at `pointer`. Returns `None` when there's no
+    /// array at `pointer`, or when one of its elements is not a number
+    pub fn sum_at(&self, pointer: &str) -> Option<f64> {
+        Some(self.numbers_at(pointer)?.iter().sum())
+    }
+
+    /// Same as [sum_at](SpannedValue::sum_at), but for the average. `None` if the array is empty
+    pub fn avg_at(&self, pointer: &str) -> Option<f64> {
+        let numbers = self.numbers_at(pointer)?;
+
+        (!numbers.is_empty()).then(|| numbers.iter().sum::<f64>() / numbers.len() as f64)
+    }
+
+    /// Same as [sum_at](SpannedValue::sum_at), but for the minimum. `None` if the array is empty
+    pub fn min_at(&self, pointer: &str) -> Option<f64> {
+        self.numbers_at(pointer)?.into_iter().reduce(f64::min)
+    }
+
+    /// Same as [sum_at](SpannedValue::sum_at), but for the maximum. `None` if the array is empty
+    pub fn max_at(&self, pointer: &str) -> Option<f64> {
+        self.numbers_at(pointer)?.into_iter().reduce(f64::max)
+    }
+
+    /// Calls `f` on the value and all its descendants, parents first
+    fn walk<F: FnMut(&SpannedValue)>(&self, f: &mut F) {
+        f(self);
+
+        match &self.value {
+            Value::Array(array) => array.iter().for_each(|v| v.walk(f)),
+            Value::Object(obj) => obj.values().for_each(|v| v.walk(f)),
+            _ => (),
+        }
+    }
+
+    /// Iterates over all the values nested in this one, depth-first and in document order:
+    /// a value is yielded before its children, and the entries of an object in the order they
+    /// appear in the source. The value itself isn't yielded
+    pub fn iter_descendants(&self) -> impl Iterator<Item = &SpannedValue> {
+        let mut stack = vec![self];
+
+        std::iter::from_fn(move || {
+            let value = stack.pop()?;
+
+            match &value.value {
+                Value::Array(array) => stack.extend(array.iter().rev()),
+                Value::Object(_) => stack.extend(
+                    value
+                        .value
+                        .object_pairs_sorted()
+                        .into_iter()
+                        .rev()
+                        .map(|(_, v)| v),
+                ),
+                _ => (),
+            }
+
+            Some(value)
+        })
+        .skip(1)
+    }
+
+    /// Lists all the values of the tree, the value itself included, with their
+    /// [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901). The values are in document
+    /// order, a value before its children, and the pointer of the value itself is empty
+    /// ```ignore
+    /// use spanned_json_parser::parse;
+    ///
+    /// fn main() {
+    ///     let parsed = parse(r#"{"server": {"port": 80, "hosts": ["a"]}}"#).unwrap();
+    ///
+    ///     for (pointer, value) in parsed.flatten() {
+    ///         println!("{} at line {}, column {}", pointer, value.start.line, value.start.col);
+    ///     }
+    /// }
+    /// ```
+    pub fn flatten(&self) -> Vec<(String, &SpannedValue)> {
+        let mut values = Vec::new();
+
+        self.flatten_at(String::new(), &mut values);
+
+        values
+    }
+
+    fn flatten_at<'a>(&'a self, pointer: String, values: &mut Vec<(String, &'a SpannedValue)>) {
+        let children: Vec<(String, &SpannedValue)> = match &self.value {
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (format!("{}/{}", pointer, i), v))
+                .collect(),
+            Value::Object(_) => self
+                .value
+                .object_pairs_sorted()
+                .into_iter()
+                .map(|(k, v)| (format!("{}/{}", pointer, escape_pointer_token(k)), v))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        values.push((pointer, self));
+
+        for (pointer, v) in children {
+            v.flatten_at(pointer, values);
+        }
+    }
+
+    /// Counts the values of each type in the tree, the value itself included
+    pub fn type_histogram(&self) -> HashMap<ValueType, usize> {
+        let mut histogram = HashMap::new();
+
+        self.walk(&mut |v| *histogram.entry(v.value.value_type()).or_insert(0) += 1);
+
+        histogram
+    }
+
+    /// Returns the path to the first value, in document order, matching `predicate`.
+    /// The path is empty when the value itself matches
+    pub fn path_to<F: Fn(&SpannedValue) -> bool>(&self, predicate: F) -> Option<Vec<PathSegment>> {
+        let mut path = Vec::new();
+
+        self.path_to_at(&predicate, &mut path).then_some(path)
+    }
+
+    fn path_to_at<F: Fn(&SpannedValue) -> bool>(
+        &self,
+        predicate: &F,
+        path: &mut Vec<PathSegment>,
+    ) -> bool {
+        if predicate(self) {
+            return true;
+        }
+
+        let children: Vec<(PathSegment, &SpannedValue)> = match &self.value {
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (PathSegment::Index(i), v))
+                .collect(),
+            Value::Object(_) => self
+                .value
+                .object_pairs_sorted()
+                .into_iter()
+                .map(|(k, v)| (PathSegment::Key(k.clone()), v))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        for (segment, v) in children {
+            path.push(segment);
+            if v.path_to_at(predicate, path) {
+                return true;
+            }
+            path.pop();
+        }
+
+        false
+    }
+}
+
+impl<K: ObjectKey> GenericSpannedValue<K> {
+    /// Checks that the start of every value is before its end, and that the span of every
+    /// value is contained in the span of its parent. Panics with the pointer of the offending
+    /// value otherwise. Enable the `debug_spans` feature to run it after each parse
+    pub fn assert_spans_valid(&self) {
+        self.assert_spans_valid_at(&mut Vec::new());
+    }
+
+    fn assert_spans_valid_at(&self, path: &mut Vec<PathSegment>) {
+        let start = (self.start.line, self.start.col);
+        let end = (self.end.line, self.end.col);
+
+        if start > end {
+            panic!(
+                "Value at '{}' starts after its end: {:?} > {:?}",
+                to_pointer(path),
+                self.start,
+                self.end
+            );
+        }
+
+        let children: Vec<(PathSegment, &Self)> = match &self.value {
+            GenericValue::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (PathSegment::Index(i), v))
+                .collect(),
+            GenericValue::Object(obj) => obj
+                .iter()
+                .map(|(k, v)| (PathSegment::Key(k.to_string()), v))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        for (segment, child) in children {
+            path.push(segment);
+
+            if (child.start.line, child.start.col) < start || (child.end.line, child.end.col) > end
+            {
+                panic!(
+                    "Value at '{}' is not contained in its parent: {:?}..{:?} is outside of {:?}..{:?}",
+                    to_pointer(path),
+                    child.start,
+                    child.end,
+                    self.start,
+                    self.end
+                );
+            }
+
+            child.assert_spans_valid_at(path);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(feature = "base64")]
+impl SpannedValue {
+    /// Decodes a string value encoded in base64, for when binary data is embedded in the json.
+    /// The error has the span of the value, which isn't a string or can't be decoded
+    pub fn as_base64_bytes(&self) -> Result<Vec<u8>, Error> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        let error = |kind| Error::new(self.start.clone(), self.end.clone(), kind);
+
+        match self.value.as_string() {
+            Some(s) => STANDARD.decode(s).map_err(|_| error(Kind::InvalidBase64)),
+            None => Err(error(Kind::NotAString)),
+        }
+    }
 }
 
 impl Display for SpannedValue {
@@ -109,20 +1253,187 @@ impl Display for SpannedValue {
 
 impl Position {
     pub fn from_ahead(val: Input<'_>) -> Self {
+        // Often times, we retrieve the position after the start or end char
+        // has already been eaten, so we need to go back by 1. After a line break, the
+        // previous char is the line break, at the end of the previous line
+        let (line, col) = val.previous_location();
+
         Self {
-            line: val.location_line(),
-            // Often times, we retrieve the position after the start or end char
-            // has already been eaten, so we need to go back by 1
-            col: val.get_utf8_column() - 1,
+            line,
+            col,
+            byte_offset: val.previous_byte_offset(),
         }
     }
 }
 
+impl std::ops::Index<&str> for SpannedValue {
+    type Output = SpannedValue;
+
+    /// Same as [SpannedValue::get], but panics when the value is not an object or has no such key
+    fn index(&self, key: &str) -> &SpannedValue {
+        self.get(key)
+            .unwrap_or_else(|| panic!("no key `{}` in {:?}", key, self.value.value_type()))
+    }
+}
+
+impl std::ops::Index<usize> for SpannedValue {
+    type Output = SpannedValue;
+
+    /// Same as [SpannedValue::get_index], but panics when the value is not an array or is too short
+    fn index(&self, index: usize) -> &SpannedValue {
+        self.get_index(index)
+            .unwrap_or_else(|| panic!("no index {} in {:?}", index, self.value.value_type()))
+    }
+}
+
+impl From<&str> for Value {
+    fn from(str: &str) -> Self {
+        Self::String(str.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(str: String) -> Self {
+        Self::String(str)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(bool: bool) -> Self {
+        Self::Bool(bool)
+    }
+}
+
+impl From<i64> for Value {
+    /// Like the parser, the positive integers are stored in [Number::PosInt]
+    fn from(num: i64) -> Self {
+        match u64::try_from(num) {
+            Ok(num) => Self::Number(Number::PosInt(num)),
+            Err(_) => Self::Number(Number::NegInt(num)),
+        }
+    }
+}
+
+impl From<u64> for Value {
+    fn from(num: u64) -> Self {
+        Self::Number(Number::PosInt(num))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(num: f64) -> Self {
+        Self::Number(Number::Float(num))
+    }
+}
+
+impl From<Vec<SpannedValue>> for Value {
+    fn from(array: Vec<SpannedValue>) -> Self {
+        Self::Array(array)
+    }
+}
+
+impl From<Value> for SpannedValue {
+    /// The value isn't parsed from a source, so its spans are left to [Position::default]
+    fn from(value: Value) -> Self {
+        Self {
+            value,
+            start: Position::default(),
+            end: Position::default(),
+            key_span: None,
+        }
+    }
+}
+
+impl TryFrom<&str> for SpannedValue {
+    type Error = crate::error::Error;
+
+    /// Same as [parse](crate::parse)
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        crate::parse(s)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<Value> for serde_json::Value {
+    /// Drops the spans. A float that json can't represent (ex: `1e400`, parsed as infinity)
+    /// becomes `null`, like [serde_json::to_value] does
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(bool) => Self::Bool(bool),
+            Value::Number(Number::PosInt(num)) => Self::Number(num.into()),
+            Value::Number(Number::NegInt(num)) => Self::Number(num.into()),
+            Value::Number(Number::Float(num) | Number::RawFloat(num, _)) => {
+                serde_json::Number::from_f64(num).map_or(Self::Null, Self::Number)
+            }
+            Value::Number(Number::Raw(text)) => text.parse().map_or(Self::Null, Self::Number),
+            Value::String(str) => Self::String(str),
+            Value::Array(array) => Self::Array(array.into_iter().map(Self::from).collect()),
+            Value::Object(obj) => {
+                let mut entries: Vec<_> = obj.into_iter().collect();
+                entries.sort_by_key(|(_, v)| (v.start.line, v.start.col));
+
+                Self::Object(entries.into_iter().map(|(k, v)| (k, v.into())).collect())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<SpannedValue> for serde_json::Value {
+    fn from(value: SpannedValue) -> Self {
+        value.value.into()
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl SpannedValue {
+    /// Converts a value built by `serde_json`, so it can be used with the API of the crate
+    /// without printing and parsing it again. As for [From<Value>](SpannedValue::from), the spans
+    /// are left to [Position::default]
+    /// ```ignore
+    /// use serde_json::json;
+    /// use spanned_json_parser::SpannedValue;
+    ///
+    /// fn main() {
+    ///     let value = SpannedValue::from_serde(&json!({"a": [1, 2.5]}));
+    ///
+    ///     assert_eq!(value.pointer("/a/1").unwrap().value.type_name(), "number");
+    /// }
+    /// ```
+    pub fn from_serde(value: &serde_json::Value) -> Self {
+        let value = match value {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(bool) => Value::Bool(*bool),
+            serde_json::Value::Number(num) => Value::Number(match (num.as_u64(), num.as_i64()) {
+                (Some(num), _) => Number::PosInt(num),
+                (None, Some(num)) => Number::NegInt(num),
+                (None, None) if num.is_f64() => Number::Float(num.as_f64().unwrap_or(f64::NAN)),
+                // An integer too wide for an u64 and an i64, or a float too big for a f64, that
+                // serde_json keeps as text when its `arbitrary_precision` feature is enabled
+                (None, None) => Number::Raw(num.to_string()),
+            }),
+            serde_json::Value::String(str) => Value::String(str.clone()),
+            serde_json::Value::Array(array) => {
+                Value::Array(array.iter().map(Self::from_serde).collect())
+            }
+            serde_json::Value::Object(obj) => Value::Object(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), Self::from_serde(v)))
+                    .collect(),
+            ),
+        };
+
+        value.into()
+    }
+}
+
 impl<'a> From<Input<'a>> for Position {
     fn from(val: Input<'a>) -> Self {
         Self {
             line: val.location_line(),
             col: val.get_utf8_column(),
+            byte_offset: val.byte_offset(),
         }
     }
 }
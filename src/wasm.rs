@@ -0,0 +1,50 @@
+use crate::error::Error;
+use crate::parser::parse;
+use crate::ser::SpannedView;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use wasm_bindgen::prelude::*;
+
+/// Error object returned to javascript, the lines and columns are 1-based like in [Error]
+struct JsError<'a>(&'a Error);
+
+impl<'a> Serialize for JsError<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Error", 5)?;
+        state.serialize_field("line", &self.0.start.line)?;
+        state.serialize_field("col", &self.0.start.col)?;
+        state.serialize_field("endLine", &self.0.end.line)?;
+        state.serialize_field("endCol", &self.0.end.col)?;
+        state.serialize_field("kind", &self.0.kind.to_string())?;
+        state.end()
+    }
+}
+
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    // Without `json_compatible`, the maps become javascript `Map` instead of plain objects
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or_else(JsValue::from)
+}
+
+/// Parses `input` and returns the value with its spans, in the format of [SpannedView]. When
+/// the input is invalid, an object `{ line, col, endLine, endCol, kind }` is returned instead,
+/// with `kind` being the message of the error
+/// ```js
+/// import { parse_json } from "spanned_json_parser";
+///
+/// const result = parse_json('{"a": tru}');
+///
+/// if ("kind" in result) {
+///     console.error(`${result.line}:${result.col}: ${result.kind}`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn parse_json(input: &str) -> JsValue {
+    match parse(input) {
+        Ok(value) => to_js(&SpannedView(&value)),
+        Err(e) => to_js(&JsError(&e)),
+    }
+}
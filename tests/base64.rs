@@ -0,0 +1,52 @@
+#![cfg(feature = "base64")]
+
+use spanned_json_parser::{error::Kind, parse, Position};
+
+#[test]
+fn valid() {
+    let parsed = parse(r#"{"data": "aGVsbG8gd29ybGQ="}"#).unwrap();
+
+    let data = parsed.find_key("data").unwrap();
+
+    assert_eq!(data.as_base64_bytes().unwrap(), b"hello world");
+}
+
+#[test]
+fn invalid() {
+    let parsed = parse(
+        r#"{
+    "data": "not base64!"
+}"#,
+    )
+    .unwrap();
+
+    let data = parsed.find_key("data").unwrap();
+
+    match data.as_base64_bytes() {
+        Err(e) => {
+            assert_eq!(
+                e.start,
+                Position {
+                    line: 2,
+                    col: 13,
+                    byte_offset: 14
+                }
+            );
+            assert_eq!(
+                e.end,
+                Position {
+                    line: 2,
+                    col: 25,
+                    byte_offset: 26
+                }
+            );
+            assert_eq!(e.kind, Kind::InvalidBase64);
+        }
+        Ok(_) => panic!("Not supposed to happen"),
+    }
+
+    match parsed.as_base64_bytes() {
+        Err(e) => assert_eq!(e.kind, Kind::NotAString),
+        Ok(_) => panic!("Not supposed to happen"),
+    }
+}
@@ -0,0 +1,187 @@
+use serde::Deserialize;
+use spanned_json_parser::{
+    error::Kind, from_spanned_value, parse, parse_with_options, ParseOptions, Position,
+};
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { width: u32, height: u32 },
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Item {
+    name: String,
+    count: i64,
+    tags: Vec<String>,
+    note: Option<String>,
+    shape: Shape,
+}
+
+#[test]
+fn struct_with_nested_values() {
+    let json = r#"{
+    "name": "box",
+    "count": -2,
+    "tags": ["a", "b"],
+    "note": null,
+    "shape": {"Rect": {"width": 3, "height": 4}}
+}"#;
+
+    let item: Item = from_spanned_value(parse(json).unwrap()).unwrap();
+
+    assert_eq!(
+        item,
+        Item {
+            name: "box".into(),
+            count: -2,
+            tags: vec!["a".into(), "b".into()],
+            note: None,
+            shape: Shape::Rect {
+                width: 3,
+                height: 4
+            },
+        }
+    );
+}
+
+#[test]
+fn enums() {
+    let shapes: Vec<Shape> =
+        from_spanned_value(parse(r#"["Empty", {"Circle": 1.5}]"#).unwrap()).unwrap();
+
+    assert_eq!(shapes, vec![Shape::Empty, Shape::Circle(1.5)]);
+}
+
+#[test]
+fn numbers() {
+    let numbers: HashMap<String, f64> =
+        from_spanned_value(parse(r#"{"pos": 1, "neg": -1, "float": 0.5}"#).unwrap()).unwrap();
+
+    assert_eq!(numbers["pos"], 1.);
+    assert_eq!(numbers["neg"], -1.);
+    assert_eq!(numbers["float"], 0.5);
+}
+
+#[test]
+fn error_spans_the_invalid_value() {
+    let json = r#"{
+    "name": "box",
+    "count": "two",
+    "tags": [],
+    "note": null,
+    "shape": "Empty"
+}"#;
+
+    match from_spanned_value::<Item>(parse(json).unwrap()) {
+        Err(e) => {
+            assert_eq!(
+                e.start,
+                Position {
+                    line: 3,
+                    col: 14,
+                    byte_offset: 34
+                }
+            );
+            assert_eq!(
+                e.end,
+                Position {
+                    line: 3,
+                    col: 18,
+                    byte_offset: 38
+                }
+            );
+            assert!(matches!(e.kind, Kind::Deserialize(_)));
+        }
+        Ok(_) => panic!("Not supposed to happen"),
+    }
+}
+
+#[test]
+fn missing_field_spans_the_object() {
+    match from_spanned_value::<Item>(parse(r#"{"name": "box"}"#).unwrap()) {
+        Err(e) => {
+            assert_eq!(e.start.col, 1);
+            assert_eq!(e.end.col, 15);
+            assert_eq!(e.kind, Kind::Deserialize("missing field `count`".into()));
+        }
+        Ok(_) => panic!("Not supposed to happen"),
+    }
+}
+
+#[test]
+fn arbitrary_precision() {
+    let options = ParseOptions {
+        arbitrary_precision: true,
+        ..Default::default()
+    };
+    let parsed = parse_with_options("[12345678901234567890123, 0.5]", &options).unwrap();
+
+    let numbers: (u128, f64) = from_spanned_value(parsed).unwrap();
+
+    assert_eq!(numbers, (12345678901234567890123, 0.5));
+}
+
+mod spanned_value_field {
+    use serde::de::value::{Error, SeqDeserializer};
+    use serde::Deserialize;
+    use spanned_json_parser::{value::Number, Position, SpannedValue};
+
+    #[derive(Deserialize)]
+    struct Wrapper {
+        id: u32,
+        payload: SpannedValue,
+        missing: Option<SpannedValue>,
+    }
+
+    #[test]
+    fn from_serde_json() {
+        let wrapper: Wrapper = serde_json::from_str(
+            r#"{"id": 1, "payload": {"a": [1, -2, 1.5, "s", null, true], "b": {}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(wrapper.id, 1);
+        assert!(wrapper.missing.is_none());
+
+        let payload = wrapper.payload;
+        assert_eq!(payload.start, Position::default());
+
+        let array = payload["a"].value.unwrap_array();
+        assert_eq!(array[0].value.unwrap_number(), &Number::PosInt(1));
+        assert_eq!(array[1].value.unwrap_number(), &Number::NegInt(-2));
+        assert_eq!(array[2].value.unwrap_number(), &Number::Float(1.5));
+        assert_eq!(array[3].value.unwrap_string(), "s");
+        array[4].value.unwrap_null();
+        assert!(array[5].value.unwrap_bool());
+        assert!(payload["b"].value.unwrap_object().is_empty());
+    }
+
+    /// Yields its items while announcing far more of them
+    struct WrongSizeHint(std::vec::IntoIter<u32>);
+
+    impl Iterator for WrongSizeHint {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<u32> {
+            self.0.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (usize::MAX / 2, Some(usize::MAX / 2))
+        }
+    }
+
+    #[test]
+    fn wrong_size_hint() {
+        let deserializer = SeqDeserializer::<_, Error>::new(WrongSizeHint(vec![1, 2].into_iter()));
+
+        let value = SpannedValue::deserialize(deserializer).unwrap();
+
+        let array = value.value.unwrap_array();
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[1].value.unwrap_number(), &Number::PosInt(2));
+    }
+}
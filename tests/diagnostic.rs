@@ -0,0 +1,166 @@
+use spanned_json_parser::{
+    diagnostic::{codeframe, line_text, LineIndex},
+    error::Kind,
+    parse, parse_with_options, ParseOptions, Position, TextRange,
+};
+
+#[test]
+fn value_codeframe() {
+    let data = r#"{
+    "name": "app",
+    "version": 1,
+    "dependencies": []
+}"#;
+
+    let parsed = parse(data).unwrap();
+    let version = parsed.find_key("version").unwrap();
+
+    assert_eq!(
+        codeframe(data, &version.span(), 1),
+        r#"2 |     "name": "app",
+3 |     "version": 1,
+  |                ^
+4 |     "dependencies": []
+"#
+    );
+}
+
+#[test]
+fn multiline_codeframe() {
+    let data = r#"{
+    "vec": [
+        1
+    ]
+}"#;
+
+    let parsed = parse(data).unwrap();
+    let vec = parsed.find_key("vec").unwrap();
+
+    assert_eq!(
+        codeframe(data, &vec.span(), 0),
+        r#"2 |     "vec": [
+  |            ^
+3 |         1
+  | ^^^^^^^^^
+4 |     ]
+  | ^^^^^
+"#
+    );
+}
+
+#[test]
+fn codeframe_at_column_zero() {
+    let span = TextRange {
+        start: Position {
+            line: 1,
+            col: 0,
+            byte_offset: 0,
+        },
+        end: Position {
+            line: 1,
+            col: 1,
+            byte_offset: 0,
+        },
+    };
+
+    assert_eq!(codeframe("]", &span, 0), "1 | ]\n  | ^\n");
+}
+
+#[test]
+fn error_ending_after_line_break() {
+    let options = ParseOptions {
+        allow_control_characters: true,
+        ..Default::default()
+    };
+
+    // The string isn't closed, the error ends on the line break that is its last char
+    for (data, end) in [
+        ("{\"a\": \"b\n", (1, 9, 8)),
+        ("{\"a\": \"b\nc\n", (2, 2, 10)),
+        ("{\"a\":\n\"b\n\n", (3, 1, 9)),
+    ] {
+        match parse_with_options(data, &options) {
+            Err(e) => {
+                assert_eq!(e.kind, Kind::MissingQuote);
+                assert_eq!((e.end.line, e.end.col, e.end.byte_offset), end);
+                assert_eq!(&data[e.end.byte_offset..e.end.byte_offset + 1], "\n");
+                // The line and column agree with the byte offset
+                assert_eq!(
+                    LineIndex::new(data).offset_to_position(e.end.byte_offset),
+                    Some(e.end)
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+#[test]
+fn line_index() {
+    let data = "{\r\n    \"name\": \"été\",\n    \"version\": 1\n}";
+
+    assert_eq!(line_text(data, 1), Some("{"));
+    assert_eq!(line_text(data, 0), None);
+
+    let index = LineIndex::new(data);
+
+    assert_eq!(index.line(2), Some("    \"name\": \"été\","));
+    assert_eq!(index.line(4), Some("}"));
+    assert_eq!(index.line(5), None);
+
+    let parsed = parse(data).unwrap();
+    let version = parsed.find_key("version").unwrap();
+    let offset = index.position_to_offset(&version.start).unwrap();
+
+    assert_eq!(&data[offset..offset + 1], "1");
+    assert_eq!(offset, version.start.byte_offset);
+
+    // The cols are counted in chars
+    let offset = index
+        .position_to_offset(&Position {
+            line: 2,
+            col: 15,
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(&data[offset..offset + 3], "té");
+    assert_eq!(
+        index.position_to_offset(&Position {
+            line: 4,
+            col: 2,
+            ..Default::default()
+        }),
+        None
+    );
+    assert_eq!(
+        index.position_to_offset(&Position {
+            line: 1,
+            col: 0,
+            ..Default::default()
+        }),
+        None
+    );
+}
+
+#[test]
+fn bom() {
+    let data = "\u{FEFF}{\"a\": tru}";
+
+    let e = parse(data).unwrap_err();
+    let span = TextRange {
+        start: e.start.clone(),
+        end: e.end.clone(),
+    };
+
+    assert_eq!(
+        codeframe(data, &span, 0),
+        "1 | {\"a\": tru}\n  |       ^^^\n"
+    );
+
+    let index = LineIndex::new(data);
+
+    assert_eq!(index.line(1), Some("{\"a\": tru}"));
+    assert_eq!(index.offset_to_position(e.start.byte_offset), Some(e.start));
+    assert_eq!(index.offset_to_position(1), None);
+}
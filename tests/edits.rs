@@ -0,0 +1,108 @@
+use spanned_json_parser::{diagnostic::LineIndex, minimal_edits, Position, TextEdit};
+
+fn apply(source: &str, edits: &[TextEdit]) -> String {
+    let index = LineIndex::new(source);
+    let offset = |position: &Position| index.position_to_offset(position).unwrap_or(source.len());
+
+    let mut result = source.to_string();
+
+    for edit in edits.iter().rev() {
+        result.replace_range(offset(&edit.start)..offset(&edit.end), &edit.replacement);
+    }
+
+    result
+}
+
+#[test]
+fn whitespace_only() {
+    let original = "{\"a\":1,\n\"b\":[1,2] }";
+    let formatted = "{\n  \"a\": 1,\n  \"b\": [1, 2]\n}";
+
+    let edits = minimal_edits(original, formatted);
+
+    assert_eq!(edits.len(), 6);
+    assert!(edits
+        .iter()
+        .all(|e| e.replacement.chars().all(char::is_whitespace)));
+    assert_eq!(
+        edits[1],
+        TextEdit {
+            start: Position {
+                line: 1,
+                col: 6,
+                byte_offset: 5
+            },
+            end: Position {
+                line: 1,
+                col: 6,
+                byte_offset: 5
+            },
+            replacement: " ".into(),
+        }
+    );
+    assert_eq!(
+        edits[2],
+        TextEdit {
+            start: Position {
+                line: 1,
+                col: 8,
+                byte_offset: 7
+            },
+            end: Position {
+                line: 2,
+                col: 1,
+                byte_offset: 8
+            },
+            replacement: "\n  ".into(),
+        }
+    );
+
+    assert_eq!(apply(original, &edits), formatted);
+}
+
+#[test]
+fn changed_value() {
+    let original = r#"{"a": "x", "b": 1}"#;
+    let formatted = r#"{"a": "y", "b": 1}"#;
+
+    let edits = minimal_edits(original, formatted);
+
+    assert_eq!(
+        edits,
+        vec![TextEdit {
+            start: Position {
+                line: 1,
+                col: 7,
+                byte_offset: 6
+            },
+            end: Position {
+                line: 1,
+                col: 10,
+                byte_offset: 9
+            },
+            replacement: r#""y""#.into(),
+        }]
+    );
+
+    let formatted = "{\n  \"a\": \"x\",\n  \"c\": [true, null]\n}";
+    let edits = minimal_edits(original, formatted);
+
+    assert_eq!(apply(original, &edits), formatted);
+}
+
+#[test]
+fn identical() {
+    assert!(minimal_edits("[1, 2]", "[1, 2]").is_empty());
+    assert_eq!(apply("", &minimal_edits("", "[]")), "[]");
+}
+
+#[test]
+fn unterminated_string_ending_with_escape() {
+    // The escaped char takes several bytes, and the string is never closed
+    for original in ["[\"a\\é", "[\"a\\é\"]", "[\"a\\🤔", "[\"a\\"] {
+        let formatted = "[\"b\"]";
+        let edits = minimal_edits(original, formatted);
+
+        assert_eq!(apply(original, &edits), formatted);
+    }
+}
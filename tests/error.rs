@@ -0,0 +1,155 @@
+mod message {
+    use spanned_json_parser::{
+        error::{English, Kind, MessageProvider},
+        parse,
+    };
+
+    struct French;
+
+    impl MessageProvider for French {
+        fn message(&self, kind: &Kind) -> String {
+            match kind {
+                Kind::MissingComma => "virgule manquante".into(),
+                kind => English.message(kind),
+            }
+        }
+    }
+
+    #[test]
+    fn custom_provider() {
+        let error = parse("[1 2]").unwrap_err();
+
+        assert_eq!(error.message(&French), "virgule manquante");
+        assert_eq!(error.message(&English), "missing comma");
+        assert_eq!(error.kind.to_string(), "missing comma");
+
+        let error = parse("[1, 2").unwrap_err();
+
+        assert_eq!(error.message(&French), "missing closing bracket `]`");
+    }
+}
+
+mod display {
+    use spanned_json_parser::{
+        error::{Encoding, Kind},
+        parse, Position, ValueType,
+    };
+
+    #[test]
+    fn includes_position() {
+        let error = parse("[1, tru]").unwrap_err();
+
+        assert_eq!(error.to_string(), "invalid value `tru` at line 1, column 5");
+
+        let error = parse("{\n  \"a\": 1,\n  \"b\": [1 2]\n}").unwrap_err();
+
+        assert_eq!(error.to_string(), "missing comma at line 3, column 9");
+    }
+
+    #[test]
+    fn missing_closing_bracket() {
+        let error = parse(r#"{"a":1"#).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "expected `}` to close the object opened at line 1, column 1"
+        );
+
+        let error = parse("{\n  \"a\": [1,\n  2").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "expected `]` to close the array opened at line 2, column 8"
+        );
+    }
+
+    #[test]
+    fn every_kind_has_a_message() {
+        let kinds = vec![
+            Kind::MissingQuote,
+            Kind::MissingArrayBracket,
+            Kind::MissingComma,
+            Kind::MissingObjectBracket,
+            Kind::InvalidKey("a".into()),
+            Kind::InvalidKey(String::new()),
+            Kind::MissingChar(':'),
+            Kind::MissingColon,
+            Kind::CharsAfterRoot {
+                token: "a".into(),
+                trailing: "a".into(),
+                parseable: false,
+            },
+            Kind::MultipleRootValues {
+                root_start: Position::default(),
+                root_end: Position::default(),
+            },
+            Kind::NotAnHex {
+                raw: "zzzz".into(),
+                position: Position::default(),
+            },
+            Kind::NotAString,
+            Kind::NotABool,
+            Kind::NotANull,
+            Kind::NotAnObject,
+            Kind::NotAnArray,
+            Kind::NotANumber,
+            Kind::InvalidValue("tru".into()),
+            Kind::TrailingComma,
+            Kind::DisallowedRootType {
+                found: ValueType::Array,
+            },
+            Kind::NonCanonicalNumber("1.50".into()),
+            Kind::InvalidBase64,
+            Kind::WrongEncoding {
+                detected: Encoding::Utf16Le,
+            },
+            Kind::InvalidUtf8,
+            Kind::DuplicateKey("a".into()),
+            Kind::DuplicateArrayItem,
+            Kind::UnterminatedComment,
+            Kind::ControlCharacterInString('\t'),
+            Kind::InvalidSurrogatePair("\\uD800".into()),
+            Kind::Deserialize("invalid type".into()),
+            Kind::Io("broken pipe".into()),
+            Kind::InvalidNumber("+1".into()),
+            Kind::LeadingZero("01".into()),
+            Kind::StringTooLong { max: 10 },
+            Kind::InputTooLarge { size: 20, max: 10 },
+            Kind::NomError(nom::error::ErrorKind::Char),
+            Kind::ToBeDefined,
+        ];
+
+        for kind in kinds {
+            let message = kind.to_string();
+            let debug = format!("{:?}", kind);
+
+            assert!(!message.is_empty());
+            assert_ne!(message, debug);
+            // The name of the variant would mean that the message comes from `Debug`
+            assert!(!message.contains(debug.split(['(', ' ']).next().unwrap()));
+        }
+
+        assert_eq!(
+            Kind::NomError(nom::error::ErrorKind::Char).to_string(),
+            "unexpected syntax (Char)"
+        );
+    }
+
+    #[test]
+    fn boxed_error() {
+        fn parse_config(s: &str) -> Result<(), Box<dyn std::error::Error>> {
+            parse(s)?;
+
+            Ok(())
+        }
+
+        assert!(parse_config("[1]").is_ok());
+
+        let error = parse_config("{\n  \"a\": \"b").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "missing closing quote at line 2, column 8"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use spanned_json_parser::{parse, reparse, value::Number, Edit, Position};
+
+#[test]
+fn reparse_leaf() {
+    let data = r#"{
+    "untouched": [1, 2, 3],
+    "edited": 1,
+    "after": "hello"
+}"#;
+
+    let previous = parse(data).unwrap();
+
+    let untouched_ptr = previous.value.unwrap_object()["untouched"]
+        .value
+        .unwrap_array()
+        .as_ptr();
+
+    let data = r#"{
+    "untouched": [1, 2, 3],
+    "edited": 1234,
+    "after": "hello"
+}"#;
+
+    let edit = Edit {
+        start: Position {
+            line: 3,
+            col: 15,
+            byte_offset: 44,
+        },
+        old_end: Position {
+            line: 3,
+            col: 15,
+            byte_offset: 44,
+        },
+        new_end: Position {
+            line: 3,
+            col: 18,
+            byte_offset: 47,
+        },
+    };
+
+    let reparsed = reparse(previous, data, &edit).unwrap();
+    let root = reparsed.value.unwrap_object();
+
+    let untouched = root.get("untouched").unwrap().value.unwrap_array();
+    assert_eq!(untouched.as_ptr(), untouched_ptr);
+
+    let edited = root.get("edited").unwrap();
+    assert_eq!(edited.value.unwrap_number(), &Number::PosInt(1234));
+    assert_eq!(
+        edited.start,
+        Position {
+            line: 3,
+            col: 15,
+            byte_offset: 44
+        }
+    );
+    assert_eq!(
+        edited.end,
+        Position {
+            line: 3,
+            col: 18,
+            byte_offset: 47
+        }
+    );
+
+    assert_eq!(reparsed, parse(data).unwrap());
+}
+
+#[test]
+fn reparse_multiline_edit() {
+    let data = r#"{"edited": [1], "after": [true, false]}"#;
+
+    let previous = parse(data).unwrap();
+
+    let data = r#"{"edited": [1,
+  2], "after": [true, false]}"#;
+
+    let edit = Edit {
+        start: Position {
+            line: 1,
+            col: 14,
+            byte_offset: 13,
+        },
+        old_end: Position {
+            line: 1,
+            col: 14,
+            byte_offset: 13,
+        },
+        new_end: Position {
+            line: 2,
+            col: 4,
+            byte_offset: 18,
+        },
+    };
+
+    let reparsed = reparse(previous, data, &edit).unwrap();
+
+    assert_eq!(reparsed, parse(data).unwrap());
+}
+
+#[test]
+fn fallback_to_full_parse() {
+    let data = r#"[1, 2]"#;
+
+    let previous = parse(data).unwrap();
+
+    // Removing the comma can't be isolated in a single value
+    let data = r#"[1 2]"#;
+
+    let edit = Edit {
+        start: Position {
+            line: 1,
+            col: 3,
+            byte_offset: 2,
+        },
+        old_end: Position {
+            line: 1,
+            col: 4,
+            byte_offset: 3,
+        },
+        new_end: Position {
+            line: 1,
+            col: 3,
+            byte_offset: 2,
+        },
+    };
+
+    let reparsed = reparse(previous, data, &edit);
+
+    match (reparsed, parse(data)) {
+        (Err(e), Err(expected)) => {
+            assert_eq!(e.kind, expected.kind);
+            assert_eq!(e.start, expected.start);
+            assert_eq!(e.end, expected.end);
+        }
+        _ => panic!("Not supposed to happen"),
+    }
+}
@@ -0,0 +1,56 @@
+use spanned_json_parser::{error::Kind, parse, parse_interned, InternedSpannedValue, Value};
+use std::sync::Arc;
+
+#[test]
+fn shared_keys() {
+    let parsed: InternedSpannedValue =
+        parse_interned(r#"[{"id": 1, "name": "a"}, {"id": 2}, {"nested": {"id": 3}}]"#).unwrap();
+
+    let array = parsed.value.as_array().unwrap();
+    let nested = array[2].value.as_object().unwrap()["nested"]
+        .value
+        .as_object()
+        .unwrap();
+
+    let ids: Vec<_> = array[..2]
+        .iter()
+        .map(|v| v.value.as_object().unwrap())
+        .chain([nested])
+        .map(|obj| obj.get_key_value("id").unwrap().0.clone())
+        .collect();
+
+    assert_eq!(ids.len(), 3);
+    assert!(ids.iter().all(|id| Arc::ptr_eq(id, &ids[0])));
+}
+
+#[test]
+fn same_as_parse() {
+    let data = r#"{"a": [1, {"b": "c"}], "d": null}"#;
+
+    let interned = parse_interned(data).unwrap();
+    let parsed = parse(data).unwrap();
+
+    assert_eq!(interned.start, parsed.start);
+    assert_eq!(interned.end, parsed.end);
+    assert_eq!(
+        serde_json::to_value(&interned).unwrap(),
+        serde_json::to_value(&parsed).unwrap()
+    );
+
+    match parse_interned(r#"{"a" 1}"#) {
+        Err(e) => assert_eq!(e.kind, Kind::MissingColon),
+        Ok(_) => panic!("Not supposed to happen"),
+    }
+}
+
+#[test]
+fn value_without_annotation() {
+    // `Value` isn't generic, so its key type doesn't have to be inferred
+    let value = Value::Bool(true);
+
+    assert_eq!(format!("{}", value), "true");
+    assert_eq!(
+        value.value_type(),
+        parse("true").unwrap().value.value_type()
+    );
+}
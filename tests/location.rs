@@ -0,0 +1,103 @@
+use spanned_json_parser::{
+    diagnostic::LineIndex,
+    location::{offset_to_position, position_to_offset},
+    parse, Position,
+};
+
+const DATA: &str = "{\n  \"🤔é\": [1, \"a\"],\r\n  \"b\": 2\n}";
+
+#[test]
+fn emoji_before_column() {
+    let offset = DATA.find('[').unwrap();
+
+    assert_eq!(
+        offset_to_position(DATA, offset),
+        Position {
+            line: 2,
+            col: 9,
+            byte_offset: offset
+        }
+    );
+    assert_eq!(
+        position_to_offset(
+            DATA,
+            &Position {
+                line: 2,
+                col: 9,
+                byte_offset: 0
+            }
+        ),
+        Some(offset)
+    );
+}
+
+#[test]
+fn roundtrip_with_parsed_spans() {
+    let parsed = parse(DATA).unwrap();
+
+    for value in std::iter::once(&parsed).chain(parsed.iter_descendants()) {
+        for position in [&value.start, &value.end] {
+            assert_eq!(&offset_to_position(DATA, position.byte_offset), position);
+            assert_eq!(
+                position_to_offset(DATA, position),
+                Some(position.byte_offset)
+            );
+        }
+    }
+}
+
+#[test]
+fn out_of_range() {
+    // Inside the emoji
+    assert_eq!(offset_to_position(DATA, 6), offset_to_position(DATA, 5));
+    assert_eq!(offset_to_position(DATA, 1000).byte_offset, DATA.len());
+    assert_eq!(offset_to_position("", 0).col, 1);
+
+    let position = |line, col| Position {
+        line,
+        col,
+        byte_offset: 0,
+    };
+
+    assert_eq!(position_to_offset(DATA, &position(5, 1)), None);
+    assert_eq!(position_to_offset(DATA, &position(0, 1)), None);
+    assert_eq!(position_to_offset(DATA, &position(1, 0)), None);
+}
+
+#[test]
+fn column_limited_to_the_line() {
+    let source = "ab\ncdef";
+    let index = LineIndex::new(source);
+    let position = |line, col| Position {
+        line,
+        col,
+        byte_offset: 0,
+    };
+
+    assert_eq!(position_to_offset(source, &position(1, 5)), None);
+    assert_eq!(position_to_offset(source, &position(1, 2)), Some(1));
+    assert_eq!(position_to_offset(source, &position(2, 4)), Some(6));
+    assert_eq!(position_to_offset(source, &position(2, 5)), None);
+
+    for col in 1..=5 {
+        assert_eq!(
+            position_to_offset(source, &position(1, col)),
+            index.position_to_offset(&position(1, col))
+        );
+    }
+}
+
+#[test]
+fn offset_inside_the_bom() {
+    let source = "\u{FEFF}[1]";
+
+    assert_eq!(
+        offset_to_position(source, 1),
+        Position {
+            line: 1,
+            col: 1,
+            byte_offset: 3
+        }
+    );
+    assert_eq!(LineIndex::new(source).offset_to_position(1), None);
+}
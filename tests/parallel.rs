@@ -0,0 +1,46 @@
+#![cfg(feature = "rayon")]
+
+use spanned_json_parser::{parse, parse_parallel};
+
+#[test]
+fn same_as_sequential() {
+    let data = r#"
+    [
+        {"hello": "world", "vec": [1, 2, {"a": "]"}]},
+        "escaped \" ]",
+        -12.5e3,
+        [[], {}],
+        "🤔", true,
+        null
+    ]
+    "#;
+
+    assert_eq!(parse_parallel(data).unwrap(), parse(data).unwrap());
+}
+
+#[test]
+fn not_an_array() {
+    let data = r#"{"hello": [1, 2]}"#;
+
+    assert_eq!(parse_parallel(data).unwrap(), parse(data).unwrap());
+}
+
+#[test]
+fn same_errors_as_sequential() {
+    let cases = ["[]", "[1 2]", "[1, 2,]", "[1, [2]", r#"["hello]"#, "[1] 2"];
+
+    for data in cases {
+        let parallel = parse_parallel(data);
+        let sequential = parse(data);
+
+        match (parallel, sequential) {
+            (Ok(parallel), Ok(sequential)) => assert_eq!(parallel, sequential),
+            (Err(parallel), Err(sequential)) => {
+                assert_eq!(parallel.start, sequential.start);
+                assert_eq!(parallel.end, sequential.end);
+                assert_eq!(parallel.kind, sequential.kind);
+            }
+            _ => panic!("Not supposed to happen"),
+        }
+    }
+}
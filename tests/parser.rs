@@ -36,7 +36,7 @@ fn parse_basic() {
     assert_eq!(world.value.unwrap_string(), "world");
     let vec = root.get("vec").unwrap().value.unwrap_array();
 
-    let num_obj = vec.get(0).unwrap().value.unwrap_object();
+    let num_obj = vec.first().unwrap().value.unwrap_object();
 
     assert_eq!(
         num_obj.get("num1").unwrap().value.unwrap_number(),
@@ -59,7 +59,7 @@ fn parse_basic() {
 }
 
 mod error {
-    use spanned_json_parser::{error::Kind, parse};
+    use spanned_json_parser::{error::Kind, parse, Position};
 
     #[test]
     fn invalid_root_json_value() {
@@ -153,6 +153,32 @@ mod error {
         }
     }
 
+    #[test]
+    fn invalid_value_span_includes_first_char() {
+        let cases = [
+            ("[tru]", "tru"),
+            ("[fals]", "fals"),
+            ("[nul]", "nul"),
+            ("[1x]", "1x"),
+            ("[xyz]", "xyz"),
+        ];
+
+        for (json, token) in cases {
+            let parsed = parse(json);
+
+            match parsed {
+                Err(e) => {
+                    assert_eq!(e.start.line, 1);
+                    assert_eq!(e.start.col, 2);
+                    assert_eq!(e.end.line, 1);
+                    assert_eq!(e.end.col, 1 + token.len());
+                    assert_eq!(e.kind, Kind::InvalidValue(token.into()));
+                }
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+    }
+
     #[test]
     fn invalid_key() {
         let json = r#"{   12: "world"}"#;
@@ -204,7 +230,7 @@ mod error {
         match parsed {
             Err(e) => {
                 assert_eq!(e.start.line, 1);
-                assert_eq!(e.start.col, 9);
+                assert_eq!(e.start.col, 2);
                 assert_eq!(e.end.line, 1);
                 assert_eq!(e.end.col, 9);
                 assert_eq!(e.kind, Kind::MissingColon);
@@ -213,6 +239,22 @@ mod error {
         }
     }
 
+    #[test]
+    fn missing_colon_multiline() {
+        let json = "{\n  \"a\": 1,\n  \"hello\"\n    \"world\"\n}";
+
+        match parse(json) {
+            Err(e) => {
+                assert_eq!(e.start.line, 3);
+                assert_eq!(e.start.col, 3);
+                assert_eq!(e.end.line, 3);
+                assert_eq!(e.end.col, 10);
+                assert_eq!(e.kind, Kind::MissingColon);
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
     #[test]
     fn nested_failure() {
         let json = r#"["hello"#;
@@ -284,7 +326,7 @@ mod error {
         match parsed {
             Err(e) => {
                 assert_eq!(e.start.line, 1);
-                assert_eq!(e.start.col, 9);
+                assert_eq!(e.start.col, 2);
                 assert_eq!(e.end.line, 1);
                 assert_eq!(e.end.col, 9);
                 assert_eq!(e.kind, Kind::MissingColon);
@@ -332,12 +374,138 @@ mod error {
             Ok(_) => panic!("Not supposed to happen"),
         }
     }
+
+    #[test]
+    fn multiple_root_values() {
+        let json = r#"{} {"a": 1}"#;
+
+        let parsed = parse(json);
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(e.start.line, 1);
+                assert_eq!(e.start.col, 4);
+                assert_eq!(e.end.line, 1);
+                assert_eq!(e.end.col, 11);
+                assert_eq!(
+                    e.kind,
+                    Kind::MultipleRootValues {
+                        root_start: Position {
+                            line: 1,
+                            col: 1,
+                            byte_offset: 0
+                        },
+                        root_end: Position {
+                            line: 1,
+                            col: 2,
+                            byte_offset: 1
+                        },
+                    }
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn chars_after_root() {
+        let json = r#"{} garbage"#;
+
+        let parsed = parse(json);
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(e.start.line, 1);
+                assert_eq!(e.start.col, 4);
+                assert_eq!(e.end.line, 1);
+                assert_eq!(e.end.col, 10);
+                assert_eq!(
+                    e.kind,
+                    Kind::CharsAfterRoot {
+                        token: "garbage".into(),
+                        trailing: "garbage".into(),
+                        parseable: false
+                    }
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        let json = r#"{} {} garbage"#;
+
+        let parsed = parse(json);
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(e.start.col, 4);
+                assert_eq!(e.end.col, 5);
+                assert_eq!(
+                    e.kind,
+                    Kind::CharsAfterRoot {
+                        token: "{}".into(),
+                        trailing: "{} garbage".into(),
+                        parseable: true
+                    }
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn chars_after_root_token() {
+        match parse("42 garbage here") {
+            Err(e) => {
+                assert_eq!(e.start.col, 4);
+                assert_eq!(e.end.col, 10);
+                assert_eq!(
+                    e.to_string(),
+                    "unexpected chars after the root value: `garbage` at line 1, column 4"
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        match parse("true false") {
+            Err(e) => {
+                assert_eq!(e.start.col, 6);
+                assert_eq!(e.end.col, 10);
+                assert!(matches!(e.kind, Kind::MultipleRootValues { .. }));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn root_error_at_first_column() {
+        let first = Position {
+            line: 1,
+            col: 1,
+            byte_offset: 0,
+        };
+
+        for (json, kind) in [
+            ("\"", Kind::MissingQuote),
+            ("]", Kind::InvalidValue("]".into())),
+        ] {
+            match parse(json) {
+                Err(e) => {
+                    assert_eq!(e.start, first, "{}", json);
+                    assert_eq!(e.end, first, "{}", json);
+                    assert_eq!(e.kind, kind);
+                }
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+    }
 }
 
 mod string {
     use spanned_json_parser::{
-        parse,
+        error::Kind,
+        parse, parse_all, parse_with_options,
         value::{Number, SpannedValue},
+        ParseOptions, Position,
     };
 
     #[test]
@@ -375,10 +543,145 @@ mod string {
         assert_eq!(key, &"foo\u{0000}bar");
         assert_eq!(num, &Number::PosInt(42));
     }
+
+    #[test]
+    fn surrogate_pair_span() {
+        let data = r#"["\uD83D\uDE00", "a\uD83D\uDE00b", 1]"#;
+
+        let parsed = parse(data).unwrap();
+
+        let array = parsed.value.unwrap_array();
+
+        assert_eq!(array[0].value.unwrap_string(), "\u{1F600}");
+        // Both escapes are covered, along with the quotes
+        assert_eq!(array[0].start.col, 2);
+        assert_eq!(array[0].end.col, 15);
+        assert_eq!(array[0].char_len(data), 14);
+
+        assert_eq!(array[1].start.col, 18);
+        assert_eq!(array[1].end.col, 33);
+        assert_eq!(array[2].start.col, 36);
+    }
+
+    #[test]
+    fn lone_surrogates() {
+        let cases = [
+            (r#""\uD800""#, "\\uD800", 2, 7),
+            (r#""\uDC00""#, "\\uDC00", 2, 7),
+            (r#"["ab\uD800\u0041"]"#, "\\uD800", 5, 10),
+        ];
+
+        for (data, escape, start, end) in cases {
+            match parse(data) {
+                Err(e) => {
+                    assert_eq!(
+                        e.kind,
+                        Kind::InvalidSurrogatePair(escape.into()),
+                        "{}",
+                        data
+                    );
+                    assert_eq!(e.start.col, start, "{}", data);
+                    assert_eq!(e.end.col, end, "{}", data);
+                }
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+
+        let parsed = parse(r#""\uD83D\uDE00""#).unwrap();
+        assert_eq!(parsed.value.unwrap_string(), "😀");
+
+        let (value, errors) = parse_all(r#"["a\uDC00b", 1]"#);
+        assert_eq!(errors.len(), 1);
+
+        let value = value.unwrap();
+        assert_eq!(
+            value.value.unwrap_array()[0].value.unwrap_string(),
+            "a\u{FFFD}b"
+        );
+    }
+
+    #[test]
+    fn control_characters() {
+        let parsed = parse("[\"a\tb\"]");
+
+        match parsed {
+            Err(e) => {
+                let position = Position {
+                    line: 1,
+                    col: 4,
+                    byte_offset: 3,
+                };
+
+                assert_eq!(e.start, position);
+                assert_eq!(e.end, position);
+                assert_eq!(e.kind, Kind::ControlCharacterInString('\t'));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        let parsed = parse("{\"key\": \"line\nbreak\"}");
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 14,
+                        byte_offset: 13
+                    }
+                );
+                assert_eq!(e.kind, Kind::ControlCharacterInString('\n'));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        let parsed = parse(r#"["a\tb\n\u001f"]"#).unwrap();
+        assert_eq!(
+            parsed.value.unwrap_array()[0].value.unwrap_string(),
+            "a\tb\n\u{1f}"
+        );
+
+        let (value, errors) = parse_all("[\"a\tb\", 1]");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(value.unwrap().value.unwrap_array().len(), 2);
+
+        let options = ParseOptions {
+            allow_control_characters: true,
+            ..Default::default()
+        };
+        let parsed = parse_with_options("[\"a\tb\nc\", 1]", &options).unwrap();
+        let array = parsed.value.unwrap_array();
+
+        assert_eq!(array[0].value.unwrap_string(), "a\tb\nc");
+        assert_eq!(array[1].start.line, 2);
+    }
+
+    #[test]
+    fn big_string() {
+        let content = "abcdéfghi\n".repeat(1_000_000);
+        let data = format!(r#"["{}\u00e9 end", 1]"#, content);
+        // The line breaks are kept as is, to check the lines of the following values
+        let options = ParseOptions {
+            allow_control_characters: true,
+            ..Default::default()
+        };
+
+        let parsed = parse_with_options(&data, &options).unwrap();
+        let array = parsed.value.unwrap_array();
+
+        let string = array[0].value.unwrap_string();
+        assert_eq!(string.len(), content.len() + 6);
+        assert!(string.starts_with(&content));
+        assert!(string.ends_with("é end"));
+
+        assert_eq!(array[0].end.line, 1_000_001);
+        assert_eq!(array[1].value.unwrap_number(), &Number::PosInt(1));
+    }
 }
 
 mod number {
-    use spanned_json_parser::{parse, value::Number};
+    use spanned_json_parser::{error::Kind, parse, value::Number};
 
     #[test]
     fn parse_exp() {
@@ -390,6 +693,29 @@ mod number {
         // assert!(parsed.is_ok());
     }
 
+    #[test]
+    fn parse_integers() {
+        let mut integers: Vec<i128> = vec![0, 9, 10, 99_999_999_999_999_999, u64::MAX as i128];
+
+        for digits in 1..=20 {
+            integers.push(10i128.pow(digits) - 1);
+            integers.push(10i128.pow(digits));
+        }
+
+        for integer in integers.iter().flat_map(|i| [*i, -i]) {
+            let parsed = parse(&integer.to_string()).unwrap();
+            let num = parsed.value.unwrap_number();
+
+            let expected = if integer >= 0 {
+                u64::try_from(integer).map(Number::PosInt).ok()
+            } else {
+                i64::try_from(integer).map(Number::NegInt).ok()
+            };
+
+            assert_eq!(num, &expected.unwrap_or(Number::Float(integer as f64)));
+        }
+    }
+
     #[test]
     fn parse_too_big_pos_int() {
         let data = "[100000000000000000000]";
@@ -398,7 +724,7 @@ mod number {
 
         assert!(parsed.is_ok());
         let parsed = parsed.unwrap();
-        let vec = parsed.value.unwrap_array().get(0).unwrap();
+        let vec = parsed.value.unwrap_array().first().unwrap();
         let num = vec.value.unwrap_number();
 
         assert_eq!(num, &Number::Float(1e20));
@@ -406,23 +732,53 @@ mod number {
 
     #[test]
     fn parse_padded_number() {
-        let data = "[01]";
-
-        let parsed = parse(data);
-
-        assert!(parsed.is_err());
-
-        let data = "[00000001]";
-
-        let parsed = parse(data);
-
-        assert!(parsed.is_err());
+        for number in ["01", "00000001", "-01", "01.5", "00e1"] {
+            let data = format!("[{}]", number);
+
+            match parse(&data) {
+                Err(e) => {
+                    assert_eq!(e.start.col, 2, "{}", number);
+                    assert_eq!(e.end.col, 1 + number.len(), "{}", number);
+                    assert_eq!(e.kind, Kind::LeadingZero(number.into()));
+                }
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
 
-        let data = "[0]";
+        for data in ["[0]", "[0.5]", "[-0]", "[0e5]", "[10]"] {
+            assert!(parse(data).is_ok(), "{}", data);
+        }
+    }
 
-        let parsed = parse(data);
+    #[test]
+    fn invalid_number() {
+        for number in ["+1", ".5", "1.", "1.e5", "-", "1e", "1e+", "-.5", "1.5.2"] {
+            let data = format!("[true, {}]", number);
+
+            match parse(&data) {
+                Err(e) => {
+                    assert_eq!(e.start.col, 8, "{}", number);
+                    assert_eq!(e.end.col, 7 + number.len(), "{}", number);
+                    assert_eq!(e.kind, Kind::InvalidNumber(number.into()));
+                }
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+    }
 
-        assert!(parsed.is_ok());
+    #[test]
+    fn valid_number() {
+        let cases = [
+            ("1.0", Number::Float(1.)),
+            ("0.5", Number::Float(0.5)),
+            ("1e5", Number::Float(1e5)),
+            ("-0.5E-2", Number::Float(-0.005)),
+            ("2e+3", Number::Float(2000.)),
+        ];
+
+        for (data, expected) in cases {
+            assert_eq!(parse(data).unwrap().value.unwrap_number(), &expected);
+        }
     }
 }
 
@@ -470,10 +826,10 @@ mod array {
 
         let parsed = parsed.unwrap();
 
-        let parsed = parsed.value.unwrap_array().get(0).unwrap();
-        let parsed = parsed.value.unwrap_array().get(0).unwrap();
-        let parsed = parsed.value.unwrap_array().get(0).unwrap();
-        let parsed = parsed.value.unwrap_array().get(0).unwrap();
+        let parsed = parsed.value.unwrap_array().first().unwrap();
+        let parsed = parsed.value.unwrap_array().first().unwrap();
+        let parsed = parsed.value.unwrap_array().first().unwrap();
+        let parsed = parsed.value.unwrap_array().first().unwrap();
 
         assert_eq!(parsed.value.unwrap_array().len(), 0);
     }
@@ -509,7 +865,7 @@ mod array {
         match parsed {
             Err(e) => {
                 assert_eq!(e.start.line, 1);
-                assert_eq!(e.start.col, 1);
+                assert_eq!(e.start.col, 2);
                 assert_eq!(e.end.line, 1);
                 assert_eq!(e.end.col, 3);
                 assert_eq!(e.kind, Kind::MissingComma)
@@ -540,7 +896,7 @@ mod array {
 }
 
 mod object {
-    use spanned_json_parser::{error::Kind, parse};
+    use spanned_json_parser::{error::Kind, parse, Position, TextRange};
 
     #[test]
     fn empty() {
@@ -605,7 +961,7 @@ mod object {
         match parsed {
             Err(e) => {
                 assert_eq!(e.start.line, 1);
-                assert_eq!(e.start.col, 1);
+                assert_eq!(e.start.col, 2);
                 assert_eq!(e.end.line, 1);
                 assert_eq!(e.end.col, 12);
                 assert_eq!(e.kind, Kind::MissingComma)
@@ -613,4 +969,1517 @@ mod object {
             Ok(_) => panic!("Not supposed to happen"),
         }
     }
+
+    #[test]
+    fn key_span() {
+        let parsed = parse(r#"{"hello": 1}"#).unwrap();
+
+        let hello = &parsed.value.unwrap_object()["hello"];
+
+        assert_eq!(
+            hello.key_span,
+            Some(TextRange {
+                start: Position {
+                    line: 1,
+                    col: 2,
+                    byte_offset: 1
+                },
+                end: Position {
+                    line: 1,
+                    col: 8,
+                    byte_offset: 7
+                }
+            })
+        );
+        assert_eq!(hello.start.col, 11);
+
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), r#"{"hello":1}"#);
+    }
+}
+
+mod options {
+    use spanned_json_parser::{
+        error::Kind, parse_with_options, to_string, value::Number, ParseOptions, Position,
+        ValueType,
+    };
+
+    #[test]
+    fn max_string_length() {
+        let options = ParseOptions {
+            max_string_length: Some(4),
+            ..Default::default()
+        };
+
+        for data in [r#"["abcd"]"#, r#"["\n\té"]"#, r#"{"abcd": ""}"#] {
+            assert!(parse_with_options(data, &options).is_ok(), "{}", data);
+        }
+
+        // Ends at the first char over the limit, with or without escapes before it
+        for (data, end) in [
+            (r#"["abcde"]"#, 7),
+            (r#"["abcdef"]"#, 7),
+            (r#"["\nabcd"]"#, 8),
+            (r#"["abcé"]"#, 6),
+            (r#"{"abcde": 1}"#, 7),
+        ] {
+            match parse_with_options(data, &options) {
+                Err(e) => {
+                    assert_eq!(e.start.col, 2, "{}", data);
+                    assert_eq!(e.end.col, end, "{}", data);
+                    assert_eq!(e.kind, Kind::StringTooLong { max: 4 });
+                }
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+    }
+
+    #[test]
+    fn max_total_bytes() {
+        let options = ParseOptions {
+            max_total_bytes: Some(8),
+            ..Default::default()
+        };
+
+        assert!(parse_with_options("[1, 2.5]", &options).is_ok());
+
+        match parse_with_options("[1, 2.50]", &options) {
+            Err(e) => {
+                assert_eq!(e.kind, Kind::InputTooLarge { size: 9, max: 8 });
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 1,
+                        byte_offset: 0
+                    }
+                );
+                assert_eq!(e.end, e.start);
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        let (value, errors) =
+            spanned_json_parser::parse_all_with_options("[1, 2.50]", &options, |_| {
+                spanned_json_parser::Recovery::Skip
+            });
+
+        assert!(value.is_none());
+        assert_eq!(errors[0].kind, Kind::InputTooLarge { size: 9, max: 8 });
+    }
+
+    #[test]
+    fn allow_leading_zeros() {
+        let options = ParseOptions {
+            allow_leading_zeros: true,
+            ..Default::default()
+        };
+
+        let parsed = parse_with_options("[01, -007, 00.5]", &options).unwrap();
+        let numbers: Vec<_> = parsed
+            .value
+            .unwrap_array()
+            .iter()
+            .map(|v| v.value.unwrap_number().clone())
+            .collect();
+
+        assert_eq!(
+            numbers,
+            vec![Number::PosInt(1), Number::NegInt(-7), Number::Float(0.5)]
+        );
+    }
+
+    #[test]
+    fn allowed_roots() {
+        let options = ParseOptions {
+            allowed_roots: Some(vec![ValueType::Object]),
+            ..Default::default()
+        };
+
+        assert!(parse_with_options(r#"{"a": [1]}"#, &options).is_ok());
+
+        let parsed = parse_with_options("  [1, 2]", &options);
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 3,
+                        byte_offset: 2
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 8,
+                        byte_offset: 7
+                    }
+                );
+                assert_eq!(
+                    e.kind,
+                    Kind::DisallowedRootType {
+                        found: ValueType::Array
+                    }
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn reject_trailing_decimal_zeros() {
+        let options = ParseOptions {
+            reject_trailing_decimal_zeros: true,
+            ..Default::default()
+        };
+
+        assert!(parse_with_options("[1.50]", &ParseOptions::default()).is_ok());
+        assert!(parse_with_options("[1.5, 10, 1.05e10, 100e2]", &options).is_ok());
+
+        let parsed = parse_with_options("[1.5, 1.50]", &options);
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 7,
+                        byte_offset: 6
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 10,
+                        byte_offset: 9
+                    }
+                );
+                assert_eq!(e.kind, Kind::NonCanonicalNumber("1.50".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        let parsed = parse_with_options("-2.10E5", &options);
+
+        match parsed {
+            Err(e) => assert_eq!(e.kind, Kind::NonCanonicalNumber("-2.10E5".into())),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn preserve_float_text() {
+        let options = ParseOptions {
+            preserve_float_text: true,
+            ..Default::default()
+        };
+
+        let floats = [
+            "1.0",
+            "1e3",
+            "3.14159265358979",
+            "-0.0",
+            "1.50",
+            "2E-7",
+            "0.1e+10",
+            "123456789012345678901234567890.5",
+        ];
+
+        for float in floats {
+            let parsed = parse_with_options(float, &options).unwrap();
+
+            assert_eq!(parsed.value.to_string(), float);
+            assert_eq!(
+                parsed.value.unwrap_number(),
+                &Number::RawFloat(float.parse().unwrap(), float.into())
+            );
+
+            assert_eq!(to_string(&parsed), float);
+            // The serde serializers format the float
+            assert_eq!(
+                serde_json::to_string(&parsed).unwrap(),
+                serde_json::to_string(&float.parse::<f64>().unwrap()).unwrap()
+            );
+        }
+
+        let parsed = parse_with_options("[1e3, 2]", &options).unwrap();
+        let array = parsed.value.unwrap_array();
+
+        assert_eq!(array[0].value.to_string(), "1e3");
+        assert_eq!(array[1].value.unwrap_number(), &Number::PosInt(2));
+
+        let parsed = parse_with_options("1e3", &ParseOptions::default()).unwrap();
+
+        assert_eq!(parsed.value.to_string(), "1000");
+    }
+
+    #[test]
+    fn arbitrary_precision() {
+        let options = ParseOptions {
+            arbitrary_precision: true,
+            ..Default::default()
+        };
+
+        for raw in [
+            "12345678901234567890123",
+            "-12345678901234567890123",
+            "0.12345678901234567890123",
+            "1e400",
+        ] {
+            let parsed = parse_with_options(raw, &options).unwrap();
+
+            assert_eq!(parsed.value.unwrap_number(), &Number::Raw(raw.into()));
+            assert_eq!(parsed.value.to_string(), raw);
+        }
+
+        let parsed = parse_with_options(
+            "[12345678901234567890123, 18446744073709551615, 1.50, 1e3]",
+            &options,
+        )
+        .unwrap();
+
+        // The serializers get the numbers that don't fit in a u64 or an i64 as floats
+        assert_eq!(
+            serde_json::to_string(&parsed).unwrap(),
+            "[1.2345678901234568e22,18446744073709551615,1.5,1000.0]"
+        );
+
+        let parsed =
+            parse_with_options("12345678901234567890123", &ParseOptions::default()).unwrap();
+
+        assert_eq!(
+            parsed.value.unwrap_number(),
+            &Number::Float(12345678901234567890123.)
+        );
+
+        match parse_with_options("[123456789012345678901a]", &options) {
+            Err(e) => assert_eq!(e.kind, Kind::InvalidValue("123456789012345678901a".into())),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn allow_non_finite_floats() {
+        let options = ParseOptions {
+            allow_non_finite_floats: true,
+            ..Default::default()
+        };
+
+        let parsed = parse_with_options("[NaN, Infinity, -Infinity]", &options).unwrap();
+        let array = parsed.value.unwrap_array();
+
+        match array[0].value.unwrap_number() {
+            Number::Float(num) => assert!(num.is_nan()),
+            _ => panic!("Not supposed to happen"),
+        }
+        assert_eq!(
+            array[1].value.unwrap_number(),
+            &Number::Float(f64::INFINITY)
+        );
+        assert_eq!(
+            array[2].value.unwrap_number(),
+            &Number::Float(f64::NEG_INFINITY)
+        );
+        assert_eq!(
+            array[2].end,
+            Position {
+                line: 1,
+                col: 25,
+                byte_offset: 24
+            }
+        );
+        assert_eq!(array[1].value.to_string(), "Infinity");
+        assert_eq!(array[2].value.to_string(), "-Infinity");
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), "[null,null,null]");
+
+        for (data, invalid) in [("Inf", "Inf"), ("-Inf", "-Inf"), ("nan", "nan")] {
+            match parse_with_options(data, &options) {
+                Err(e) => assert_eq!(e.kind, Kind::InvalidValue(invalid.into())),
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+
+        for data in ["NaN", "Infinity", "-Infinity", "-inf"] {
+            match parse_with_options(data, &ParseOptions::default()) {
+                Err(e) => assert_eq!(e.kind, Kind::InvalidValue(data.into())),
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+    }
+
+    #[test]
+    fn error_on_duplicate_keys() {
+        let options = ParseOptions {
+            error_on_duplicate_keys: true,
+            ..Default::default()
+        };
+
+        match parse_with_options(r#"{"a": 1, "a": 2}"#, &options) {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 10,
+                        byte_offset: 9
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 12,
+                        byte_offset: 11
+                    }
+                );
+                assert_eq!(e.kind, Kind::DuplicateKey("a".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        let parsed = parse_with_options(r#"{"a": 1, "a": 2}"#, &ParseOptions::default()).unwrap();
+        let obj = parsed.value.unwrap_object();
+
+        assert_eq!(obj.len(), 1);
+        assert_eq!(obj["a"].value.unwrap_number(), &Number::PosInt(2));
+        assert_eq!(obj["a"].key_span.as_ref().unwrap().start.col, 10);
+    }
+}
+
+mod bare_word_strings {
+    use spanned_json_parser::{error::Kind, parse, parse_with_options, ParseOptions, Position};
+
+    #[test]
+    fn strict() {
+        let parsed = parse(r#"{"a": yes}"#);
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 7,
+                        byte_offset: 6
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 9,
+                        byte_offset: 8
+                    }
+                );
+                assert_eq!(e.kind, Kind::InvalidValue("yes".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn relaxed() {
+        let options = ParseOptions {
+            allow_bare_word_strings: true,
+            ..Default::default()
+        };
+
+        let parsed = parse_with_options(r#"{"a": yes}"#, &options).unwrap();
+
+        let a = parsed.find_key("a").unwrap();
+        assert_eq!(a.value.unwrap_string(), "yes");
+        assert_eq!(
+            a.start,
+            Position {
+                line: 1,
+                col: 7,
+                byte_offset: 6
+            }
+        );
+        assert_eq!(
+            a.end,
+            Position {
+                line: 1,
+                col: 9,
+                byte_offset: 8
+            }
+        );
+
+        let parsed =
+            parse_with_options("[no, off, true, false, null, nullable, on_1]", &options).unwrap();
+
+        let array = parsed.value.unwrap_array();
+        assert_eq!(array[0].value.unwrap_string(), "no");
+        assert_eq!(array[1].value.unwrap_string(), "off");
+        assert!(array[2].value.unwrap_bool());
+        assert!(!array[3].value.unwrap_bool());
+        array[4].value.unwrap_null();
+        assert_eq!(array[5].value.unwrap_string(), "nullable");
+        assert_eq!(array[6].value.unwrap_string(), "on_1");
+
+        match parse_with_options("[y'es]", &options) {
+            Err(e) => assert_eq!(e.kind, Kind::InvalidValue("y'es".into())),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod invalid_value_extends_to_whitespace {
+    use spanned_json_parser::{error::Kind, parse, parse_with_options, ParseOptions, Position};
+
+    #[test]
+    fn wider_span() {
+        let options = ParseOptions {
+            invalid_value_extends_to_whitespace: true,
+            ..Default::default()
+        };
+
+        match parse_with_options("'sussy string'", &options) {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 1,
+                        byte_offset: 0
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 14,
+                        byte_offset: 13
+                    }
+                );
+                assert_eq!(e.kind, Kind::InvalidValue("'sussy string'".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        match parse_with_options(r#"{"hello": not a value , "b": 1}"#, &options) {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 11,
+                        byte_offset: 10
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 21,
+                        byte_offset: 20
+                    }
+                );
+                assert_eq!(e.kind, Kind::InvalidValue("not a value".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn starting_with_a_digit() {
+        let options = ParseOptions {
+            invalid_value_extends_to_whitespace: true,
+            ..Default::default()
+        };
+
+        match parse_with_options("[1x y, 2]", &options) {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 2,
+                        byte_offset: 1
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 5,
+                        byte_offset: 4
+                    }
+                );
+                assert_eq!(e.kind, Kind::InvalidValue("1x y".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        match parse("[1x y, 2]") {
+            Err(e) => assert_eq!(e.kind, Kind::InvalidValue("1x".into())),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod leading_whitespace {
+    use spanned_json_parser::{parse, parse_with_options, ParseOptions, Position};
+
+    const DATA: &str = r#"{
+    "key":   "value",
+    "vec": [
+        1,   2
+    ]
+}"#;
+
+    #[test]
+    fn excluded_by_default() {
+        let parsed = parse(DATA).unwrap();
+
+        let value = parsed.find_key("key").unwrap();
+        assert_eq!(
+            value.start,
+            Position {
+                line: 2,
+                col: 14,
+                byte_offset: 15
+            }
+        );
+
+        let one = &parsed.find_key("vec").unwrap().value.unwrap_array()[0];
+        assert_eq!(
+            one.start,
+            Position {
+                line: 4,
+                col: 9,
+                byte_offset: 45
+            }
+        );
+    }
+
+    #[test]
+    fn included_with_option() {
+        let options = ParseOptions {
+            include_leading_whitespace_in_span: true,
+            ..Default::default()
+        };
+
+        let parsed = parse_with_options(DATA, &options).unwrap();
+
+        let value = parsed.find_key("key").unwrap();
+        assert_eq!(
+            value.start,
+            Position {
+                line: 2,
+                col: 11,
+                byte_offset: 12
+            }
+        );
+        assert_eq!(
+            value.end,
+            Position {
+                line: 2,
+                col: 20,
+                byte_offset: 21
+            }
+        );
+
+        let vec = parsed.find_key("vec").unwrap().value.unwrap_array();
+        assert_eq!(
+            vec[0].start,
+            Position {
+                line: 4,
+                col: 1,
+                byte_offset: 37
+            }
+        );
+        assert_eq!(
+            vec[1].start,
+            Position {
+                line: 4,
+                col: 11,
+                byte_offset: 47
+            }
+        );
+        assert_eq!(
+            vec[1].end,
+            Position {
+                line: 4,
+                col: 14,
+                byte_offset: 50
+            }
+        );
+    }
+}
+
+mod parse_all {
+    use spanned_json_parser::{
+        error::Kind, parse_all, parse_all_with_options, value::Number, ParseOptions, Position,
+        Recovery,
+    };
+
+    #[test]
+    fn collects_errors() {
+        let (value, errors) = parse_all("[1 2, tru, 3");
+
+        let kinds: Vec<Kind> = errors.into_iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::MissingComma,
+                Kind::InvalidValue("tru".into()),
+                Kind::MissingArrayBracket
+            ]
+        );
+
+        let value = value.unwrap();
+        let array = value.value.unwrap_array();
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array[2].value.unwrap_number(), &Number::PosInt(3));
+        assert_eq!(
+            array[2].start,
+            Position {
+                line: 1,
+                col: 12,
+                byte_offset: 11
+            }
+        );
+    }
+
+    #[test]
+    fn skips_invalid_pairs() {
+        let (value, errors) = parse_all(r#"{"a" 1, "b": [x, 2], "c": 3,}"#);
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].kind, Kind::MissingColon);
+        assert_eq!(errors[1].kind, Kind::InvalidValue("x".into()));
+        assert_eq!(errors[2].kind, Kind::TrailingComma);
+
+        let value = value.unwrap();
+        let obj = value.value.unwrap_object();
+
+        assert!(obj.get("a").is_none());
+        assert_eq!(obj["b"].value.unwrap_array().len(), 1);
+        assert_eq!(obj["c"].value.unwrap_number(), &Number::PosInt(3));
+    }
+
+    #[test]
+    fn missing_comma_in_object() {
+        let (value, errors) = parse_all("{\n  \"a\": 1\n  \"b\": {\"c\": 2 \"d\": 3}\n}");
+
+        let kinds: Vec<Kind> = errors.iter().map(|e| e.kind.clone()).collect();
+        assert_eq!(kinds, vec![Kind::MissingComma, Kind::MissingComma]);
+        // The errors span from the entry missing its comma to the unexpected key
+        assert_eq!((errors[0].start.line, errors[0].start.col), (2, 3));
+        assert_eq!(errors[0].end.line, 3);
+        assert_eq!(errors[1].start.col, 9);
+        assert_eq!(errors[1].end.col, 15);
+
+        let value = value.unwrap();
+        let obj = value.value.unwrap_object();
+
+        assert_eq!(obj["a"].value.unwrap_number(), &Number::PosInt(1));
+
+        let nested = obj["b"].value.unwrap_object();
+        assert_eq!(nested["c"].value.unwrap_number(), &Number::PosInt(2));
+        assert_eq!(nested["d"].value.unwrap_number(), &Number::PosInt(3));
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let options = ParseOptions {
+            error_on_duplicate_keys: true,
+            ..Default::default()
+        };
+        let data = r#"{
+    "first": {"a": 1, "b": 2, "a": 3},
+    "second": {"c": 4, "c": 5}
+}"#;
+
+        let (value, errors) = parse_all_with_options(data, &options, |_| Recovery::Skip);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].kind, Kind::DuplicateKey("a".into()));
+        assert_eq!(
+            errors[0].start,
+            Position {
+                line: 2,
+                col: 31,
+                byte_offset: 32
+            }
+        );
+        assert_eq!(
+            errors[0].end,
+            Position {
+                line: 2,
+                col: 33,
+                byte_offset: 34
+            }
+        );
+        assert_eq!(errors[1].kind, Kind::DuplicateKey("c".into()));
+        assert_eq!(
+            errors[1].start,
+            Position {
+                line: 3,
+                col: 24,
+                byte_offset: 64
+            }
+        );
+        assert_eq!(
+            errors[1].end,
+            Position {
+                line: 3,
+                col: 26,
+                byte_offset: 66
+            }
+        );
+
+        // The first occurrence is kept
+        let value = value.unwrap();
+        let first = value.value.unwrap_object()["first"].value.unwrap_object();
+        assert_eq!(first["a"].value.unwrap_number(), &Number::PosInt(1));
+    }
+
+    #[test]
+    fn valid() {
+        let (value, errors) = parse_all(r#"{"a": [1, 2]}"#);
+
+        assert!(value.is_some());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn invalid_root() {
+        let (value, errors) = parse_all("tru");
+
+        assert!(value.is_none());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn abort() {
+        let mut calls = 0;
+
+        let (value, errors) =
+            parse_all_with_options("[1 2 3 4 5]", &ParseOptions::default(), |_| {
+                calls += 1;
+
+                if calls == 2 {
+                    Recovery::Abort
+                } else {
+                    Recovery::Skip
+                }
+            });
+
+        assert!(value.is_none());
+        assert_eq!(calls, 2);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.kind == Kind::MissingComma));
+    }
+}
+
+mod peek_root_type {
+    use spanned_json_parser::{error::Kind, peek_root_type, Position, ValueType};
+
+    #[test]
+    fn types() {
+        let cases = [
+            (r#"{"a": 1}"#, ValueType::Object),
+            ("\n  [1, 2]", ValueType::Array),
+            (r#""hello""#, ValueType::String),
+            ("-12", ValueType::Number),
+            ("0.5", ValueType::Number),
+            ("true", ValueType::Bool),
+            ("false", ValueType::Bool),
+            ("null", ValueType::Null),
+        ];
+
+        for (json, expected) in cases {
+            assert_eq!(peek_root_type(json).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(peek_root_type("").is_err());
+        assert!(peek_root_type("  \n ").is_err());
+
+        match peek_root_type("  hello") {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 3,
+                        byte_offset: 2
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 7,
+                        byte_offset: 6
+                    }
+                );
+                assert_eq!(e.kind, Kind::InvalidValue("hello".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod parse_bytes {
+    use spanned_json_parser::{
+        error::{Encoding, Kind},
+        parse_bytes, Position,
+    };
+
+    fn utf16_le(s: &str, bom: bool) -> Vec<u8> {
+        let bom = bom.then_some(0xFEFF);
+
+        bom.into_iter()
+            .chain(s.encode_utf16())
+            .flat_map(|c| c.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn utf8() {
+        let parsed = parse_bytes(r#"{"a": [1, 2]}"#.as_bytes()).unwrap();
+
+        assert_eq!(parsed.find_key("a").unwrap().value.unwrap_array().len(), 2);
+    }
+
+    #[test]
+    fn utf16() {
+        let json = r#"{"a": [1, 2]}"#;
+
+        for bytes in [utf16_le(json, true), utf16_le(json, false)] {
+            match parse_bytes(&bytes) {
+                Err(e) => {
+                    assert_eq!(
+                        e.kind,
+                        Kind::WrongEncoding {
+                            detected: Encoding::Utf16Le
+                        }
+                    );
+                    assert!(e.kind.to_string().contains("UTF-16 LE"));
+                }
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+
+        let bytes: Vec<u8> = json.encode_utf16().flat_map(|c| c.to_be_bytes()).collect();
+
+        match parse_bytes(&bytes) {
+            Err(e) => assert_eq!(
+                e.kind,
+                Kind::WrongEncoding {
+                    detected: Encoding::Utf16Be
+                }
+            ),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn utf32() {
+        let bytes: Vec<u8> = "[1]"
+            .chars()
+            .flat_map(|c| (c as u32).to_le_bytes())
+            .collect();
+
+        match parse_bytes(&bytes) {
+            Err(e) => {
+                assert_eq!(
+                    e.kind,
+                    Kind::WrongEncoding {
+                        detected: Encoding::Utf32Le
+                    }
+                );
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 1,
+                        byte_offset: 0
+                    }
+                );
+                assert_eq!(e.end, e.start);
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut bytes = b"{\n  \"a\": \"".to_vec();
+        bytes.extend([0xC3, 0x28, b'"', b'}']);
+
+        match parse_bytes(&bytes) {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 2,
+                        col: 9,
+                        byte_offset: 10
+                    }
+                );
+                assert_eq!(e.kind, Kind::InvalidUtf8);
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod auto_close_brackets {
+    use spanned_json_parser::{
+        error::Kind, parse_all_with_options, parse_with_options, parse_with_warnings,
+        value::Number, ParseOptions, Position, Recovery,
+    };
+
+    fn options() -> ParseOptions {
+        ParseOptions {
+            auto_close_brackets: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn truncated() {
+        let (value, warnings) = parse_with_warnings(r#"{"a":[1,2"#, &options()).unwrap();
+
+        let array = value.value.unwrap_object()["a"].value.unwrap_array();
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[1].value.unwrap_number(), &Number::PosInt(2));
+
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].kind, Kind::MissingArrayBracket);
+        assert_eq!(
+            warnings[0].start,
+            Position {
+                line: 1,
+                col: 6,
+                byte_offset: 5
+            }
+        );
+        assert_eq!(
+            warnings[0].end,
+            Position {
+                line: 1,
+                col: 9,
+                byte_offset: 8
+            }
+        );
+        assert_eq!(warnings[1].kind, Kind::MissingObjectBracket);
+        assert_eq!(
+            warnings[1].start,
+            Position {
+                line: 1,
+                col: 1,
+                byte_offset: 0
+            }
+        );
+        assert_eq!(
+            warnings[1].end,
+            Position {
+                line: 1,
+                col: 9,
+                byte_offset: 8
+            }
+        );
+
+        assert_eq!(
+            value.end,
+            Position {
+                line: 1,
+                col: 9,
+                byte_offset: 8
+            }
+        );
+    }
+
+    #[test]
+    fn trailing_comma_and_empty() {
+        let (value, warnings) = parse_with_warnings("[1, [\n", &options()).unwrap();
+
+        let array = value.value.unwrap_array();
+        assert_eq!(array.len(), 2);
+        assert!(array[1].value.unwrap_array().is_empty());
+        assert_eq!(warnings.len(), 2);
+
+        assert!(parse_with_options("[1, [", &options()).is_ok());
+    }
+
+    #[test]
+    fn only_at_the_end() {
+        let parsed = parse_with_options(r#"{"a": [1, 2}"#, &options());
+
+        match parsed {
+            Err(e) => assert_eq!(e.kind, Kind::MissingComma),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        let parsed = parse_with_options(r#"{"a": "b"#, &options());
+
+        match parsed {
+            Err(e) => assert_eq!(e.kind, Kind::MissingQuote),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn disabled() {
+        let (value, warnings) = parse_with_warnings("[1, 2]", &options()).unwrap();
+
+        assert_eq!(value.value.unwrap_array().len(), 2);
+        assert!(warnings.is_empty());
+
+        let parsed = parse_with_options("[1, 2", &ParseOptions::default());
+
+        match parsed {
+            Err(e) => assert_eq!(e.kind, Kind::MissingArrayBracket),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        // parse_all keeps reporting them as errors
+        let (value, errors) = parse_all_with_options("[1, 2", &options(), |_| Recovery::Skip);
+
+        assert!(value.is_some());
+        assert_eq!(errors[0].kind, Kind::MissingArrayBracket);
+    }
+}
+
+mod require_unique_array_items {
+    use spanned_json_parser::{
+        error::Kind, parse_all_with_options, parse_with_options, ParseOptions, Position, Recovery,
+    };
+
+    fn options() -> ParseOptions {
+        ParseOptions {
+            require_unique_array_items: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn duplicate() {
+        let parsed = parse_with_options("[1, 2, 1]", &options());
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(e.kind, Kind::DuplicateArrayItem);
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 8,
+                        byte_offset: 7
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 8,
+                        byte_offset: 7
+                    }
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        assert!(parse_with_options("[1, 2, 1]", &ParseOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn structural() {
+        let data = r#"[{"a": [1], "b": 2}, {"b": 2,
+  "a": [ 1 ]}, {"a": [1]}]"#;
+
+        let (value, errors) = parse_all_with_options(data, &options(), |_| Recovery::Skip);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].start,
+            Position {
+                line: 1,
+                col: 22,
+                byte_offset: 21
+            }
+        );
+        assert_eq!(
+            errors[0].end,
+            Position {
+                line: 2,
+                col: 13,
+                byte_offset: 42
+            }
+        );
+        assert_eq!(value.unwrap().value.unwrap_array().len(), 2);
+
+        assert!(parse_with_options(r#"[1, "1", [1], {"1": 1}]"#, &options()).is_ok());
+    }
+
+    #[test]
+    fn many_items() {
+        // Comparing every pair of items would be quadratic
+        let items: Vec<String> = (0..20_000).map(|i| format!("[{}]", i)).collect();
+        let data = format!("[{}, [42]]", items.join(", "));
+
+        match parse_with_options(&data, &options()) {
+            Err(e) => {
+                assert_eq!(e.kind, Kind::DuplicateArrayItem);
+                assert_eq!(&data[e.start.byte_offset..=e.end.byte_offset], "[42]");
+                assert_eq!(e.start.byte_offset, data.len() - 5);
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        assert!(parse_with_options(&format!("[{}]", items.join(", ")), &options()).is_ok());
+    }
+}
+
+mod byte_offset {
+    use spanned_json_parser::{diagnostic::LineIndex, error::Kind, parse, Position, SpannedValue};
+
+    fn check(value: &SpannedValue, index: &LineIndex) {
+        assert_eq!(
+            index.position_to_offset(&value.start),
+            Some(value.start.byte_offset)
+        );
+        assert_eq!(
+            index.position_to_offset(&value.end),
+            Some(value.end.byte_offset)
+        );
+
+        if let Some(key_span) = &value.key_span {
+            assert_eq!(
+                index.position_to_offset(&key_span.start),
+                Some(key_span.start.byte_offset)
+            );
+            assert_eq!(
+                index.position_to_offset(&key_span.end),
+                Some(key_span.end.byte_offset)
+            );
+        }
+
+        match &value.value {
+            spanned_json_parser::Value::Array(array) => array.iter().for_each(|v| check(v, index)),
+            spanned_json_parser::Value::Object(obj) => obj.values().for_each(|v| check(v, index)),
+            _ => (),
+        }
+    }
+
+    #[test]
+    fn matches_line_and_col() {
+        let data = "{\r\n  \"clé\": \"été\",\n  \"🦀\": [1.50, true, null, {\"a\": \"\\u00e9\"}],\n  \"b\": -12\n}";
+
+        let parsed = parse(data).unwrap();
+
+        check(&parsed, &LineIndex::new(data));
+
+        let number = &parsed.value.unwrap_object()["🦀"].value.unwrap_array()[0];
+        assert_eq!(
+            &data[number.start.byte_offset..=number.end.byte_offset],
+            "1.50"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        match parse("[\"été") {
+            Err(e) => {
+                assert_eq!(e.kind, Kind::MissingQuote);
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 1,
+                        col: 5,
+                        byte_offset: 5,
+                    }
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        match parse("[1, é]") {
+            Err(e) => {
+                assert_eq!(e.kind, Kind::InvalidValue("é".into()));
+                assert_eq!(e.start.byte_offset, 4);
+                assert_eq!(e.end.byte_offset, 4);
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod allow_comments {
+    use spanned_json_parser::{error::Kind, parse, parse_with_options, ParseOptions, Position};
+
+    fn options() -> ParseOptions {
+        ParseOptions {
+            allow_comments: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn comments() {
+        let data = r#"// The config
+{
+    /* The name */ "name": "app", // Trailing
+    "list" /* before colon */ : [1, /* inside */ 2,
+        // Last one
+        3
+    ] /* after */
+}
+// End"#;
+
+        let parsed = parse_with_options(data, &options()).unwrap();
+        let obj = parsed.value.unwrap_object();
+
+        assert_eq!(obj["name"].value.unwrap_string(), "app");
+        assert_eq!(
+            obj["name"].start,
+            Position {
+                line: 3,
+                col: 28,
+                byte_offset: 43,
+            }
+        );
+
+        let list = obj["list"].value.unwrap_array();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list[2].start.line, 6);
+
+        assert!(parse_with_options("[1, 2 /**/]", &options()).is_ok());
+        assert!(parse_with_options("/* a */ 1 /* b */", &options()).is_ok());
+    }
+
+    #[test]
+    fn disabled() {
+        assert!(parse("[1, /* inside */ 2]").is_err());
+        assert!(parse_with_options("[1] // end", &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn unterminated() {
+        let parsed = parse_with_options("[1, /* inside\n 2]", &options());
+
+        match parsed {
+            Err(e) => {
+                assert_eq!(e.kind, Kind::UnterminatedComment);
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 1,
+                        col: 5,
+                        byte_offset: 4,
+                    }
+                );
+                assert_eq!(
+                    e.end,
+                    Position {
+                        line: 2,
+                        col: 3,
+                        byte_offset: 16,
+                    }
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod line_numbers {
+    use spanned_json_parser::{parse, parse_all};
+
+    #[test]
+    fn never_zero() {
+        let data = r#"
+{
+    "a": [1, -2.5, "x", true, null],
+    "b": {"c": {"d": []}, "e": "é"},
+
+    "f": [[{}], {"g": false}]
+}
+"#;
+
+        let parsed = parse(data).unwrap();
+
+        assert!(parsed.start.line >= 1);
+
+        for value in parsed.iter_descendants() {
+            assert!(value.start.line >= 1, "{:?}", value);
+            assert!(value.end.line >= value.start.line, "{:?}", value);
+
+            if let Some(key_span) = &value.key_span {
+                assert!(key_span.start.line >= 1, "{:?}", value);
+            }
+        }
+
+        let (_, errors) = parse_all("[1,\n 2 3,\n tru]");
+
+        assert!(!errors.is_empty());
+        assert!(errors.iter().all(|e| e.start.line >= 1 && e.end.line >= 1));
+    }
+}
+
+mod parse_prefix {
+    use spanned_json_parser::{error::Kind, parse_prefix, value::Number, Position};
+
+    #[test]
+    fn stops_after_value() {
+        let (value, offset) = parse_prefix(r#"{"a":1}rest"#).unwrap();
+
+        assert_eq!(offset, 7);
+        assert!(value.get("a").is_some());
+        assert_eq!(
+            value.end,
+            Position {
+                line: 1,
+                col: 7,
+                byte_offset: 6
+            }
+        );
+    }
+
+    #[test]
+    fn concatenated_values() {
+        let data = "{\"id\": 1}\n{\"id\": 2}\n";
+
+        let (first, offset) = parse_prefix(data).unwrap();
+        let (second, _) = parse_prefix(&data[offset..]).unwrap();
+
+        assert_eq!(offset, 9);
+        assert_eq!(first["id"].value.unwrap_number(), &Number::PosInt(1));
+        assert_eq!(second["id"].value.unwrap_number(), &Number::PosInt(2));
+        // The positions are relative to the slice, which starts with the line break
+        assert_eq!(second.start.line, 2);
+    }
+
+    #[test]
+    fn invalid_value() {
+        match parse_prefix("  tru rest") {
+            Err(e) => assert_eq!(e.kind, Kind::InvalidValue("tru".into())),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod parse_lines {
+    use spanned_json_parser::{error::Kind, parse, parse_lines, Position};
+
+    #[test]
+    fn positions_relative_to_input() {
+        let data = "{\"a\": 1}\n\n  \r\n  [true, \"é\"]\r\n\"last\"";
+
+        let values: Vec<_> = parse_lines(data).collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(values.len(), 3);
+        assert_eq!(
+            values[1].start,
+            Position {
+                line: 4,
+                col: 3,
+                byte_offset: 16
+            }
+        );
+        assert_eq!(
+            values[1].end,
+            Position {
+                line: 4,
+                col: 13,
+                byte_offset: 27
+            }
+        );
+        assert_eq!(values[2].start.line, 5);
+        assert_eq!(&data[values[2].start.byte_offset..], "\"last\"");
+        assert_eq!(values[0], parse("{\"a\": 1}").unwrap());
+    }
+
+    #[test]
+    fn errors_dont_stop_iteration() {
+        let results: Vec<_> = parse_lines("1\ntru\n2 3\n[4").collect();
+
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_ok());
+
+        match &results[1] {
+            Err(e) => {
+                assert_eq!(e.start.line, 2);
+                assert_eq!(e.kind, Kind::InvalidValue("tru".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        match &results[2] {
+            Err(e) => {
+                assert_eq!(e.start.line, 3);
+                assert_eq!(e.start.col, 3);
+                assert!(matches!(e.kind, Kind::MultipleRootValues { .. }));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        match &results[3] {
+            Err(e) => assert_eq!(e.kind, Kind::MissingArrayBracket),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod bom {
+    use spanned_json_parser::{
+        error::Kind, location::offset_to_position, parse, parse_all, parse_bytes, Position,
+    };
+
+    #[test]
+    fn skipped() {
+        let data = "\u{FEFF}{\"a\":1}";
+
+        let parsed = parse(data).unwrap();
+
+        assert_eq!(
+            parsed.start,
+            Position {
+                line: 1,
+                col: 1,
+                byte_offset: 3
+            }
+        );
+        assert_eq!(
+            parsed["a"].start,
+            Position {
+                line: 1,
+                col: 6,
+                byte_offset: 8
+            }
+        );
+        assert_eq!(parsed["a"].source(data), "1");
+        assert_eq!(offset_to_position(data, 8), parsed["a"].start);
+        assert_eq!(parse_bytes(data.as_bytes()).unwrap(), parsed);
+    }
+
+    #[test]
+    fn error() {
+        let (_, errors) = parse_all("\u{FEFF}[1,\n tru]");
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].start.line, 2);
+        assert_eq!(errors[0].start.col, 2);
+        assert_eq!(errors[0].kind, Kind::InvalidValue("tru".into()));
+    }
+
+    #[test]
+    fn only_at_start() {
+        match parse("[\u{FEFF}1]") {
+            Err(e) => assert_eq!(e.kind, Kind::InvalidValue("\u{FEFF}1".into())),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
 }
@@ -0,0 +1,52 @@
+#![cfg(feature = "preserve_order")]
+
+mod ordered_merge {
+    use spanned_json_parser::{parse, value::Number};
+
+    #[test]
+    fn three_layers() {
+        let mut merged = parse(r#"{"name": "app", "port": 80, "log": {"level": "info"}}"#).unwrap();
+
+        let env =
+            parse(r#"{"log": {"file": "app.log", "level": "warn"}, "debug": false}"#).unwrap();
+        let local = parse(r#"{"workers": 4, "port": 8080, "name": "local"}"#).unwrap();
+
+        merged.ordered_merge(&env);
+        merged.ordered_merge(&local);
+
+        let root = merged.value.unwrap_object();
+
+        let keys: Vec<&str> = root.keys().map(|k| k.as_str()).collect();
+        assert_eq!(keys, vec!["name", "port", "log", "debug", "workers"]);
+
+        let log = root["log"].value.unwrap_object();
+
+        let keys: Vec<&str> = log.keys().map(|k| k.as_str()).collect();
+        assert_eq!(keys, vec!["level", "file"]);
+
+        assert_eq!(log["level"].value.unwrap_string(), "warn");
+        assert_eq!(root["port"].value.unwrap_number(), &Number::PosInt(8080));
+        assert_eq!(root["name"].value.unwrap_string(), "local");
+    }
+}
+
+mod serialize {
+    use spanned_json_parser::parse;
+
+    #[test]
+    fn keys_keep_source_order() {
+        let json = r#"{"z":1,"a":{"y":[true,null],"b":"c"},"m":2.5}"#;
+
+        let parsed = parse(json).unwrap();
+
+        let keys: Vec<&str> = parsed
+            .value
+            .unwrap_object()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        assert_eq!(keys, vec!["z", "a", "m"]);
+
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+    }
+}
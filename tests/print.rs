@@ -0,0 +1,64 @@
+use spanned_json_parser::{parse, parse_with_options, to_string, to_string_pretty, ParseOptions};
+
+#[test]
+fn compact() {
+    let parsed = parse(
+        r#"{ "z": [1, -2, 1.5, 3.0], "a": {"b": null, "c": [true, false]}, "e": [], "o": {} }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        to_string(&parsed),
+        r#"{"z":[1,-2,1.5,3.0],"a":{"b":null,"c":[true,false]},"e":[],"o":{}}"#
+    );
+}
+
+#[test]
+fn pretty() {
+    let parsed = parse(r#"{"a": [1, {"b": "c"}], "d": {}, "e": []}"#).unwrap();
+
+    assert_eq!(
+        to_string_pretty(&parsed, 2),
+        r#"{
+  "a": [
+    1,
+    {
+      "b": "c"
+    }
+  ],
+  "d": {},
+  "e": []
+}"#
+    );
+    assert_eq!(to_string_pretty(&parse("[1]").unwrap(), 0), "[\n1\n]");
+}
+
+#[test]
+fn escapes() {
+    let parsed = parse(r#""quote \" backslash \\ slash \/ \b\f\n\r\t \u0001 é 😀""#).unwrap();
+
+    assert_eq!(
+        to_string(&parsed),
+        r#""quote \" backslash \\ slash / \b\f\n\r\t \u0001 é 😀""#
+    );
+}
+
+#[test]
+fn numbers() {
+    let options = ParseOptions {
+        preserve_float_text: true,
+        arbitrary_precision: true,
+        allow_non_finite_floats: true,
+        ..Default::default()
+    };
+    let parsed = parse_with_options(
+        "[1e3, 1.50, 12345678901234567890123, NaN, -Infinity]",
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(
+        to_string(&parsed),
+        "[1e3,1.50,12345678901234567890123,null,null]"
+    );
+}
@@ -0,0 +1,58 @@
+use proptest::prelude::*;
+use serde_json::{Map, Number, Value};
+use spanned_json_parser::{parse, to_string, to_string_pretty};
+
+fn json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<u64>().prop_map(|n| Value::Number(n.into())),
+        any::<i64>().prop_map(|n| Value::Number(n.into())),
+        any::<f64>()
+            .prop_filter_map("Json numbers are finite", Number::from_f64)
+            .prop_map(Value::Number),
+        any::<String>().prop_map(Value::String),
+    ];
+
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+            prop::collection::vec((any::<String>(), inner), 0..8)
+                .prop_map(|entries| Value::Object(entries.into_iter().collect::<Map<_, _>>())),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn parse_serialize_parse(value in json()) {
+        let source = serde_json::to_string(&value).unwrap();
+
+        let mut parsed = parse(&source).unwrap();
+        prop_assert_eq!(&serde_json::to_value(&parsed).unwrap(), &value);
+
+        let serialized = serde_json::to_string(&parsed).unwrap();
+        let mut reparsed = parse(&serialized).unwrap();
+
+        // The spans differ as the serialized source isn't formatted the same way
+        parsed.strip_spans();
+        reparsed.strip_spans();
+
+        prop_assert_eq!(parsed, reparsed);
+    }
+
+    #[test]
+    fn print_parse(value in json()) {
+        let mut parsed = parse(&serde_json::to_string(&value).unwrap()).unwrap();
+
+        let mut compact = parse(&to_string(&parsed)).unwrap();
+        let mut pretty = parse(&to_string_pretty(&parsed, 2)).unwrap();
+
+        parsed.strip_spans();
+        compact.strip_spans();
+        pretty.strip_spans();
+
+        prop_assert_eq!(&parsed, &compact);
+        prop_assert_eq!(&parsed, &pretty);
+    }
+}
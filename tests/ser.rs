@@ -0,0 +1,35 @@
+use spanned_json_parser::{parse, parse_with_options, to_string, ParseOptions};
+
+#[test]
+fn numeric_keys_stay_strings() {
+    let parsed = parse(r#"{"123": "x"}"#).unwrap();
+
+    let serialized = serde_json::to_string(&parsed).unwrap();
+
+    assert_eq!(serialized, r#"{"123":"x"}"#);
+
+    let value = serde_json::to_value(&parsed).unwrap();
+    let key = value.as_object().unwrap().keys().next().unwrap();
+
+    assert_eq!(key, "123");
+}
+
+#[test]
+fn raw_numbers() {
+    let options = ParseOptions {
+        arbitrary_precision: true,
+        ..Default::default()
+    };
+    // The integers are wider than 128 bits
+    let data = "[340282366920938463463374607431768211456,-340282366920938463463374607431768211457,\
+0.12345678901234567890123]";
+
+    let parsed = parse_with_options(data, &options).unwrap();
+
+    // The serializers get the floats, only `to_string` keeps the text
+    assert_eq!(
+        serde_json::to_string(&parsed).unwrap(),
+        "[3.402823669209385e38,-3.402823669209385e38,0.12345678901234568]"
+    );
+    assert_eq!(to_string(&parsed), data);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "serde_json")]
+
+use serde_json::json;
+use spanned_json_parser::{parse, value::Number, Position, SpannedValue, Value};
+
+#[test]
+fn same_as_serde_json() {
+    let data = r#"{"null": null, "bool": true, "pos": 18446744073709551615, "neg": -9223372036854775808,
+"float": 1.5, "str": "s\n", "array": [1, [2]], "object": {"a": {}}}"#;
+
+    let parsed = parse(data).unwrap();
+
+    let value: serde_json::Value = parsed.into();
+
+    assert_eq!(
+        value,
+        serde_json::from_str::<serde_json::Value>(data).unwrap()
+    );
+}
+
+#[test]
+fn numbers_too_big() {
+    let value: serde_json::Value = parse("[18446744073709551616, -9223372036854775809, 1e400]")
+        .unwrap()
+        .into();
+
+    assert_eq!(
+        value,
+        json!([18446744073709551616.0, -9223372036854775809.0, null])
+    );
+}
+
+#[test]
+fn from_value() {
+    let value: serde_json::Value = parse(r#"{"a": [1, "b"]}"#).unwrap().value.into();
+
+    assert_eq!(value, json!({"a": [1, "b"]}));
+}
+
+#[test]
+fn from_serde() {
+    let data = r#"{"null": null, "bool": true, "pos": 18446744073709551615, "neg": -9223372036854775808,
+"float": 1.5, "whole_float": 2.0, "str": "s\n", "array": [1, [2]], "object": {"a": {}}}"#;
+
+    let serde: serde_json::Value = serde_json::from_str(data).unwrap();
+    let value = SpannedValue::from_serde(&serde);
+
+    assert!(value.value.value_eq(&parse(data).unwrap().value));
+    assert_eq!(value.start, Position::default());
+    assert_eq!(value.end, Position::default());
+
+    let obj = value.value.unwrap_object();
+
+    assert_eq!(obj["pos"].value, Value::Number(Number::PosInt(u64::MAX)));
+    assert_eq!(obj["neg"].value, Value::Number(Number::NegInt(i64::MIN)));
+    assert_eq!(obj["whole_float"].value, Value::Number(Number::Float(2.0)));
+    assert_eq!(obj["object"].key_span, None);
+}
+
+#[test]
+fn round_trip() {
+    let serde = json!({"a": [1, -2, 2.5, 3.0], "b": {"c": null, "d": "e"}, "f": false});
+
+    assert_eq!(
+        serde_json::Value::from(SpannedValue::from_serde(&serde)),
+        serde
+    );
+
+    let parsed = parse(r#"[{"a": 1.0}, -1, "b", [true, null]]"#).unwrap();
+    let back = SpannedValue::from_serde(&parsed.clone().into());
+
+    assert!(back.value.value_eq(&parsed.value));
+}
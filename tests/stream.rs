@@ -0,0 +1,160 @@
+use spanned_json_parser::{error::Kind, parse, parse_stream, Position};
+use std::io::Read;
+
+/// Returns the data one byte at a time, to split the elements and the utf8 chars across reads
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> Read for ByteReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.0.split_first() {
+            Some((byte, rest)) => {
+                buf[0] = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("disk error"))
+    }
+}
+
+mod elements {
+    use super::*;
+
+    #[test]
+    fn same_spans_as_parse() {
+        let data = r#"  [
+    {"msg": "héllo, [world]", "level": 1},
+    "escaped \" ] quote",
+    [1, [2, {}]], -1.5e3 , null,true
+]
+"#;
+
+        let parsed = parse(data).unwrap();
+        let expected = parsed.value.unwrap_array();
+
+        let elements: Vec<_> = parse_stream(data.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(&elements, expected);
+
+        let elements: Vec<_> = parse_stream(ByteReader(data.as_bytes()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(&elements, expected);
+    }
+
+    #[test]
+    fn empty_array() {
+        assert_eq!(parse_stream(" [ ] ".as_bytes()).count(), 0);
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn invalid_element() {
+        let mut stream = parse_stream("[1,\n tru, 3]".as_bytes());
+
+        assert!(stream.next().unwrap().is_ok());
+
+        match stream.next().unwrap() {
+            Err(e) => {
+                assert_eq!(
+                    e.start,
+                    Position {
+                        line: 2,
+                        col: 2,
+                        byte_offset: 5
+                    }
+                );
+                assert_eq!(e.kind, Kind::InvalidValue("tru".into()));
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn not_an_array() {
+        match parse_stream(r#"{"a": 1}"#.as_bytes()).next().unwrap() {
+            Err(e) => assert_eq!(e.kind, Kind::NotAnArray),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn missing_bracket() {
+        let results: Vec<_> = parse_stream("[1, 2".as_bytes()).collect();
+
+        assert_eq!(results.len(), 2);
+
+        match &results[1] {
+            Err(e) => {
+                assert_eq!(e.start.col, 1);
+                assert_eq!(e.end.col, 6);
+                assert_eq!(e.kind, Kind::MissingArrayBracket);
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn separators() {
+        let cases = [
+            ("[1 2]", Kind::MissingComma),
+            ("[1,]", Kind::TrailingComma),
+            ("[,1]", Kind::InvalidValue(",".into())),
+        ];
+
+        for (data, kind) in cases {
+            let last = parse_stream(data.as_bytes()).last().unwrap();
+
+            match last {
+                Err(e) => assert_eq!(e.kind, kind, "{}", data),
+                Ok(_) => panic!("Not supposed to happen"),
+            }
+        }
+    }
+
+    #[test]
+    fn chars_after_root() {
+        let last = parse_stream("[1] 2".as_bytes()).last().unwrap();
+
+        match last {
+            Err(e) => {
+                assert_eq!(e.start.col, 5);
+                assert_eq!(
+                    e.kind,
+                    Kind::CharsAfterRoot {
+                        token: "2".into(),
+                        trailing: "2".into(),
+                        parseable: true
+                    }
+                );
+            }
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+
+    #[test]
+    fn io() {
+        let results: Vec<_> = parse_stream(FailingReader).collect();
+
+        assert_eq!(results.len(), 1);
+
+        match &results[0] {
+            Err(e) => assert_eq!(e.kind, Kind::Io("disk error".into())),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use spanned_json_parser::{parse, parse_with_transforms, Pattern, Transform};
+
+#[test]
+fn expand_home() {
+    let data = r#"{
+    "paths": {"config": "$HOME/.config", "cache": "$HOME/.cache"},
+    "name": "$HOME",
+    "nested": [{"path": "$HOME/a"}, {"path": "$HOME/b", "other": "$HOME"}]
+}"#;
+
+    let transforms: Vec<(Pattern, Transform)> = vec![
+        (
+            Pattern::new("/paths/*"),
+            Box::new(|s| s.replace("$HOME", "/home/user")),
+        ),
+        (
+            Pattern::new("/nested/*/path"),
+            Box::new(|s| s.replace("$HOME", "~")),
+        ),
+    ];
+
+    let parsed = parse_with_transforms(data, &transforms).unwrap();
+
+    let config = parsed.find_key("config").unwrap();
+    assert_eq!(config.value.unwrap_string(), "/home/user/.config");
+    assert_eq!(
+        parsed.find_key("cache").unwrap().value.unwrap_string(),
+        "/home/user/.cache"
+    );
+    assert_eq!(
+        parsed.find_key("name").unwrap().value.unwrap_string(),
+        "$HOME"
+    );
+
+    let nested = parsed.find_all_keys("path");
+    assert_eq!(nested[0].1.value.unwrap_string(), "~/a");
+    assert_eq!(nested[1].1.value.unwrap_string(), "~/b");
+    assert_eq!(
+        parsed.find_key("other").unwrap().value.unwrap_string(),
+        "$HOME"
+    );
+
+    // The spans are the ones of the original strings
+    let original = parse(data).unwrap();
+    assert_eq!(config.span(), original.find_key("config").unwrap().span());
+}
@@ -0,0 +1,1318 @@
+mod find_key {
+    use spanned_json_parser::{parse, value::Number, PathSegment};
+
+    const DATA: &str = r#"{
+    "name": "app",
+    "version": 1,
+    "dependencies": [
+        {"name": "nom", "version": 7},
+        {"name": "serde", "options": {"version": 2}}
+    ]
+}"#;
+
+    #[test]
+    fn single_match() {
+        let parsed = parse(DATA).unwrap();
+
+        let version = parsed.find_key("version").unwrap();
+
+        assert_eq!(version.value.unwrap_number(), &Number::PosInt(1));
+        assert_eq!(version.start.line, 3);
+        assert_eq!(version.start.col, 16);
+
+        let options = parsed.find_key("options").unwrap();
+
+        assert_eq!(options.start.line, 6);
+        assert!(parsed.find_key("missing").is_none());
+    }
+
+    #[test]
+    fn multi_match() {
+        let parsed = parse(DATA).unwrap();
+
+        let found = parsed.find_all_keys("version");
+
+        assert_eq!(found.len(), 3);
+
+        let (path, value) = &found[0];
+        assert_eq!(path, &vec![PathSegment::Key("version".into())]);
+        assert_eq!(value.value.unwrap_number(), &Number::PosInt(1));
+
+        let (path, value) = &found[1];
+        assert_eq!(
+            path,
+            &vec![
+                PathSegment::Key("dependencies".into()),
+                PathSegment::Index(0),
+                PathSegment::Key("version".into())
+            ]
+        );
+        assert_eq!(value.value.unwrap_number(), &Number::PosInt(7));
+        assert_eq!(value.start.line, 5);
+
+        let (path, value) = &found[2];
+        assert_eq!(
+            path,
+            &vec![
+                PathSegment::Key("dependencies".into()),
+                PathSegment::Index(1),
+                PathSegment::Key("options".into()),
+                PathSegment::Key("version".into())
+            ]
+        );
+        assert_eq!(value.value.unwrap_number(), &Number::PosInt(2));
+    }
+}
+
+mod spanned_view {
+    use spanned_json_parser::{parse, SpannedView};
+
+    #[test]
+    fn serialize_spans() {
+        let parsed = parse(r#"{"a":1}"#).unwrap();
+
+        let serialized = serde_json::to_value(SpannedView(&parsed)).unwrap();
+
+        assert_eq!(
+            serialized,
+            serde_json::json!({
+                "value": {
+                    "a": {
+                        "value": 1,
+                        "start": { "line": 1, "col": 6 },
+                        "end": { "line": 1, "col": 6 }
+                    }
+                },
+                "start": { "line": 1, "col": 1 },
+                "end": { "line": 1, "col": 7 }
+            })
+        );
+    }
+}
+
+mod object_pairs_sorted {
+    use spanned_json_parser::parse;
+
+    #[test]
+    fn source_order() {
+        let data = r#"{
+    "zebra": 1,
+    "apple": 2, "mango": 3,
+    "banana": {"y": 1, "x": 2}
+}"#;
+
+        let parsed = parse(data).unwrap();
+
+        let keys: Vec<&str> = parsed
+            .value
+            .object_pairs_sorted()
+            .into_iter()
+            .map(|(k, _)| k.as_str())
+            .collect();
+
+        assert_eq!(keys, vec!["zebra", "apple", "mango", "banana"]);
+
+        let parsed = parse("[1]").unwrap();
+
+        assert!(parsed.value.object_pairs_sorted().is_empty());
+    }
+}
+
+mod assert_spans_valid {
+    use spanned_json_parser::{parse, Position};
+
+    #[test]
+    fn complex_document() {
+        let data = r#"
+    {
+        "hello": "world",
+        "emoji🤔": "🤔🤔",
+        "vec": [
+            {
+        "num1": 1,
+        "num2": 1.2,
+        "num3": 1.2e12,
+        "num4": -12
+    }, [], {}, [[["deep"]]]
+        ],
+    "is": false,
+    "is_not": true,
+    "empty": null
+    }
+    "#;
+
+        parse(data).unwrap().assert_spans_valid();
+    }
+
+    #[test]
+    #[should_panic(expected = "Value at '/a~1b' is not contained in its parent")]
+    fn child_outside_of_parent() {
+        let mut parsed = parse(r#"{"a/b": 1}"#).unwrap();
+
+        if let spanned_json_parser::Value::Object(obj) = &mut parsed.value {
+            obj.get_mut("a/b").unwrap().end = Position {
+                line: 2,
+                col: 1,
+                byte_offset: 0,
+            };
+        }
+
+        parsed.assert_spans_valid();
+    }
+}
+
+mod strip_spans {
+    use spanned_json_parser::{parse, Position, SpannedValue, Value};
+
+    fn assert_stripped(value: &SpannedValue) {
+        assert_eq!(value.start, Position::default());
+        assert_eq!(value.end, Position::default());
+
+        match &value.value {
+            Value::Array(array) => array.iter().for_each(assert_stripped),
+            Value::Object(obj) => obj.values().for_each(assert_stripped),
+            _ => (),
+        }
+    }
+
+    #[test]
+    fn all_positions_are_default() {
+        let mut parsed = parse(r#"{"a": [1, {"b": null}], "c": "d"}"#).unwrap();
+
+        parsed.strip_spans();
+
+        assert_stripped(&parsed);
+        assert_eq!(parsed.find_key("c").unwrap().value.unwrap_string(), "d");
+    }
+}
+
+mod char_len {
+    use spanned_json_parser::parse;
+
+    #[test]
+    fn escaped_string() {
+        let data = r#"{"escaped": "a\né🤔"}"#;
+
+        let parsed = parse(data).unwrap();
+        let escaped = parsed.find_key("escaped").unwrap();
+
+        assert_eq!(escaped.value.unwrap_string().chars().count(), 4);
+        assert_eq!(escaped.char_len(data), 7);
+    }
+
+    #[test]
+    fn multiline() {
+        let data = "{\"vec\": [\n  1,\n  2\n]}";
+
+        let parsed = parse(data).unwrap();
+        let vec = parsed.find_key("vec").unwrap();
+
+        assert_eq!(vec.char_len(data), 12);
+        assert_eq!(parsed.char_len(data), data.chars().count());
+    }
+}
+
+mod path_to {
+    use spanned_json_parser::{parse, PathSegment, Value};
+
+    #[test]
+    fn nested_string() {
+        let parsed = parse(
+            r#"{
+    "name": "app",
+    "dependencies": [
+        {"name": "nom"},
+        {"name": "serde", "features": ["derive", "std"]}
+    ]
+}"#,
+        )
+        .unwrap();
+
+        let path = parsed.path_to(|v| v.value == Value::String("std".into()));
+
+        assert_eq!(
+            path,
+            Some(vec![
+                PathSegment::Key("dependencies".into()),
+                PathSegment::Index(1),
+                PathSegment::Key("features".into()),
+                PathSegment::Index(1)
+            ])
+        );
+
+        let path = parsed.path_to(|v| v.value == Value::String("nom".into()));
+
+        assert_eq!(path.unwrap().len(), 3);
+        assert_eq!(parsed.path_to(|_| true), Some(vec![]));
+        assert_eq!(parsed.path_to(|v| v.value == Value::Null), None);
+    }
+}
+
+mod merge_with_conflicts {
+    use spanned_json_parser::{parse, value::Number, Position, TextRange, ValueType};
+
+    #[test]
+    fn replaced_type() {
+        let mut base = parse(r#"{"name": "app", "port": "80", "log": {"level": 1}}"#).unwrap();
+        let overlay =
+            parse(r#"{"port": 8080, "name": "local", "log": {"level": "warn"}}"#).unwrap();
+
+        let conflicts = base.merge_with_conflicts(&overlay);
+
+        assert_eq!(conflicts.len(), 2);
+
+        let port = conflicts.iter().find(|c| c.pointer == "/port").unwrap();
+
+        assert_eq!(port.base_type, ValueType::String);
+        assert_eq!(port.overlay_type, ValueType::Number);
+        assert_eq!(
+            port.base,
+            TextRange {
+                start: Position {
+                    line: 1,
+                    col: 25,
+                    byte_offset: 24
+                },
+                end: Position {
+                    line: 1,
+                    col: 28,
+                    byte_offset: 27
+                }
+            }
+        );
+        assert_eq!(
+            port.overlay,
+            TextRange {
+                start: Position {
+                    line: 1,
+                    col: 10,
+                    byte_offset: 9
+                },
+                end: Position {
+                    line: 1,
+                    col: 13,
+                    byte_offset: 12
+                }
+            }
+        );
+
+        let level = conflicts
+            .iter()
+            .find(|c| c.pointer == "/log/level")
+            .unwrap();
+
+        assert_eq!(level.base_type, ValueType::Number);
+        assert_eq!(level.overlay_type, ValueType::String);
+
+        assert_eq!(
+            base.find_key("port").unwrap().value.unwrap_number(),
+            &Number::PosInt(8080)
+        );
+        assert_eq!(
+            base.find_key("name").unwrap().value.unwrap_string(),
+            "local"
+        );
+    }
+}
+
+mod entries {
+    use spanned_json_parser::{parse, Position, TextRange};
+
+    #[test]
+    fn key_spans() {
+        let parsed = parse(r#"{"hello": "world"}"#).unwrap();
+
+        let entries: Vec<_> = parsed.value.entries().collect();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "hello");
+        assert_eq!(
+            entries[0].key_span,
+            TextRange {
+                start: Position {
+                    line: 1,
+                    col: 2,
+                    byte_offset: 1
+                },
+                end: Position {
+                    line: 1,
+                    col: 8,
+                    byte_offset: 7
+                }
+            }
+        );
+        assert_eq!(entries[0].value.value.unwrap_string(), "world");
+    }
+
+    #[test]
+    fn document_order() {
+        let parsed = parse(
+            r#"{
+    "b": 1,
+    "a": {"nested": true}
+}"#,
+        )
+        .unwrap();
+
+        let keys: Vec<(&str, usize)> = parsed
+            .value
+            .entries()
+            .map(|e| (e.key, e.key_span.start.line))
+            .collect();
+
+        assert_eq!(keys, vec![("b", 2), ("a", 3)]);
+
+        let nested = parsed
+            .find_key("a")
+            .unwrap()
+            .value
+            .entries()
+            .next()
+            .unwrap();
+
+        assert_eq!(
+            nested.key_span.start,
+            Position {
+                line: 3,
+                col: 11,
+                byte_offset: 24
+            }
+        );
+        assert_eq!(
+            nested.key_span.end,
+            Position {
+                line: 3,
+                col: 18,
+                byte_offset: 31
+            }
+        );
+        assert!(parse("[1]").unwrap().value.entries().next().is_none());
+    }
+}
+
+mod apply_defaults {
+    use spanned_json_parser::{parse, value::Number, Position};
+
+    #[test]
+    fn missing_keys() {
+        let mut config =
+            parse(r#"{"name": "app", "log": {"level": "warn"}, "port": null}"#).unwrap();
+
+        let defaults = parse(
+            r#"{
+    "name": "default",
+    "port": 80,
+    "log": {"level": "info", "file": "app.log"},
+    "workers": 4
+}"#,
+        )
+        .unwrap();
+
+        config.apply_defaults(&defaults);
+
+        let root = config.value.unwrap_object();
+
+        assert_eq!(root.len(), 4);
+        assert_eq!(root["name"].value.unwrap_string(), "app");
+        root["port"].value.unwrap_null();
+
+        let workers = &root["workers"];
+        assert_eq!(workers.value.unwrap_number(), &Number::PosInt(4));
+        assert_eq!(
+            workers.start,
+            Position {
+                line: 5,
+                col: 16,
+                byte_offset: 105
+            }
+        );
+
+        let log = root["log"].value.unwrap_object();
+
+        assert_eq!(log["level"].value.unwrap_string(), "warn");
+        assert_eq!(log["file"].value.unwrap_string(), "app.log");
+    }
+}
+
+mod type_histogram {
+    use spanned_json_parser::{parse, ValueType};
+    use std::collections::HashMap;
+
+    #[test]
+    fn mixed_document() {
+        let parsed =
+            parse(r#"{"a": [1, 2.5, -3], "b": {"c": "d", "e": null}, "f": [true, []]}"#).unwrap();
+
+        let expected: HashMap<ValueType, usize> = [
+            (ValueType::Object, 2),
+            (ValueType::Array, 3),
+            (ValueType::Number, 3),
+            (ValueType::String, 1),
+            (ValueType::Null, 1),
+            (ValueType::Bool, 1),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(parsed.type_histogram(), expected);
+    }
+}
+
+mod date_strings {
+    use spanned_json_parser::{parse, Position};
+
+    #[test]
+    fn tagged() {
+        let parsed = parse(r#"{"t":"2023-01-01T00:00:00Z"}"#).unwrap();
+
+        let dates = parsed.date_strings();
+
+        assert_eq!(dates.len(), 1);
+        assert_eq!(dates[0].0, "/t");
+        assert_eq!(dates[0].1.value.unwrap_string(), "2023-01-01T00:00:00Z");
+        assert_eq!(
+            dates[0].1.start,
+            Position {
+                line: 1,
+                col: 6,
+                byte_offset: 5
+            }
+        );
+        assert_eq!(
+            dates[0].1.end,
+            Position {
+                line: 1,
+                col: 27,
+                byte_offset: 26
+            }
+        );
+    }
+
+    #[test]
+    fn formats() {
+        let parsed = parse(
+            r#"[
+    "2023-01-01",
+    "2023-12-31T23:59",
+    "2023-06-15T12:30:00.123+02:00",
+    "2023-06-15T12:30:60-05:30",
+    "2023-13-01",
+    "2023-01-01T",
+    "2023-01-01T24:00:00Z",
+    "2023-01-01T00:00:00.Z",
+    "01/01/2023",
+    "hello",
+    20230101
+]"#,
+        )
+        .unwrap();
+
+        let pointers: Vec<String> = parsed.date_strings().into_iter().map(|(p, _)| p).collect();
+
+        assert_eq!(pointers, vec!["/0", "/1", "/2", "/3"]);
+    }
+}
+
+mod map_strings {
+    use spanned_json_parser::parse;
+
+    #[test]
+    fn trim() {
+        let data = r#"{" key ": "  value ", "vec": [" a", {"b": "b  "}, 1]}"#;
+
+        let original = parse(data).unwrap();
+        let mut parsed = original.clone();
+
+        parsed.map_strings(|s| s.trim().to_string());
+
+        let root = parsed.value.unwrap_object();
+        let value = &root[" key "];
+        let vec = root["vec"].value.unwrap_array();
+
+        assert_eq!(value.value.unwrap_string(), "value");
+        assert_eq!(vec[0].value.unwrap_string(), "a");
+        assert_eq!(vec[1].find_key("b").unwrap().value.unwrap_string(), "b");
+
+        let original_value = original.find_key(" key ").unwrap();
+        assert_eq!(value.start, original_value.start);
+        assert_eq!(value.end, original_value.end);
+    }
+}
+
+mod aggregates {
+    use spanned_json_parser::parse;
+
+    #[test]
+    fn numbers_by_pointer() {
+        let parsed =
+            parse(r#"{"metrics": {"a/b": [1, 2, 3.5]}, "empty": [], "mixed": [1, "2"]}"#).unwrap();
+
+        assert_eq!(parsed.sum_at("/metrics/a~1b"), Some(6.5));
+        assert_eq!(parsed.avg_at("/metrics/a~1b"), Some(6.5 / 3.0));
+        assert_eq!(parsed.min_at("/metrics/a~1b"), Some(1.0));
+        assert_eq!(parsed.max_at("/metrics/a~1b"), Some(3.5));
+
+        assert_eq!(parsed.sum_at("/empty"), Some(0.0));
+        assert_eq!(parsed.avg_at("/empty"), None);
+        assert_eq!(parsed.max_at("/empty"), None);
+
+        assert_eq!(parsed.sum_at("/mixed"), None);
+        assert_eq!(parsed.sum_at("/metrics"), None);
+        assert_eq!(parsed.sum_at("/missing"), None);
+
+        let parsed = parse("[-1, 2]").unwrap();
+
+        assert_eq!(parsed.sum_at(""), Some(1.0));
+    }
+}
+
+mod deep_get {
+    use spanned_json_parser::{parse, Position};
+
+    #[test]
+    fn nested_keys() {
+        let parsed = parse(
+            r#"{"config": {"database": {"host": "localhost", "a/b": 1}, "list": [{"host": 2}]}}"#,
+        )
+        .unwrap();
+
+        let host = parsed.deep_get(&["config", "database", "host"]).unwrap();
+        assert_eq!(host.value.unwrap_string(), "localhost");
+        assert_eq!(
+            host.start,
+            Position {
+                line: 1,
+                col: 34,
+                byte_offset: 33
+            }
+        );
+
+        assert!(parsed.deep_get(&["config", "database", "a/b"]).is_some());
+        assert_eq!(parsed.deep_get(&[]), Some(&parsed));
+        assert!(parsed.deep_get(&["config", "missing"]).is_none());
+        assert!(parsed.deep_get(&["config", "list", "0"]).is_none());
+        assert!(parsed
+            .deep_get(&["config", "database", "host", "x"])
+            .is_none());
+    }
+}
+
+mod array_element_types {
+    use spanned_json_parser::{parse, ValueType};
+    use std::collections::HashSet;
+
+    #[test]
+    fn homogeneous() {
+        let parsed = parse("[1, 2, 3]").unwrap();
+
+        assert_eq!(
+            parsed.value.array_element_types(),
+            Some(HashSet::from([ValueType::Number]))
+        );
+        assert!(parsed.value.is_homogeneous_array());
+
+        assert!(parse("[]").unwrap().value.is_homogeneous_array());
+    }
+
+    #[test]
+    fn mixed() {
+        let parsed = parse(r#"[1, "a"]"#).unwrap();
+
+        assert_eq!(
+            parsed.value.array_element_types(),
+            Some(HashSet::from([ValueType::Number, ValueType::String]))
+        );
+        assert!(!parsed.value.is_homogeneous_array());
+    }
+
+    #[test]
+    fn not_an_array() {
+        let parsed = parse(r#"{"a": [1]}"#).unwrap();
+
+        assert_eq!(parsed.value.array_element_types(), None);
+        assert!(!parsed.value.is_homogeneous_array());
+    }
+}
+
+mod try_from {
+    use spanned_json_parser::{error::Kind, value::Number, SpannedValue};
+
+    #[test]
+    fn try_into() {
+        let value: SpannedValue = "[1, 2]".try_into().unwrap();
+
+        assert_eq!(
+            value.value.unwrap_array()[1].value.unwrap_number(),
+            &Number::PosInt(2)
+        );
+
+        let value: Result<SpannedValue, _> = "[1, 2".try_into();
+
+        match value {
+            Err(e) => assert_eq!(e.kind, Kind::MissingArrayBracket),
+            Ok(_) => panic!("Not supposed to happen"),
+        }
+    }
+}
+
+mod into_iter {
+    use spanned_json_parser::{parse, value::PathSegment, SpannedValue};
+
+    #[test]
+    fn drain_object() {
+        let parsed = parse(r#"{"b": [1], "a": "x", "c": null}"#).unwrap();
+
+        let entries: Vec<(String, SpannedValue)> = parsed
+            .value
+            .into_iter()
+            .map(|(segment, value)| match segment {
+                PathSegment::Key(key) => (key, value),
+                PathSegment::Index(_) => panic!("Not supposed to happen"),
+            })
+            .collect();
+
+        let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["b", "a", "c"]);
+        assert_eq!(entries[1].1.value.unwrap_string(), "x");
+    }
+
+    #[test]
+    fn drain_array() {
+        let parsed = parse(r#"["a", "b"]"#).unwrap();
+
+        let elements: Vec<_> = parsed.value.into_iter().collect();
+
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[1].0, PathSegment::Index(1));
+        assert_eq!(elements[1].1.value.unwrap_string(), "b");
+
+        assert_eq!(parse("1").unwrap().value.into_iter().count(), 0);
+    }
+}
+
+mod refs {
+    use spanned_json_parser::{parse, Position, TextRange};
+
+    #[test]
+    fn collects_refs() {
+        let data = r##"{
+    "definitions": {"Foo": {"type": "string"}},
+    "properties": {
+        "a": {"$ref": "#/definitions/Foo"},
+        "b": [{"$ref": "#/definitions/Bar"}],
+        "c": {"$ref": "#/definitions/Foo", "description": "not a pure ref"},
+        "d": {"$ref": 1}
+    }
+}"##;
+
+        let parsed = parse(data).unwrap();
+
+        assert_eq!(
+            parsed.refs(),
+            vec![
+                (
+                    "#/definitions/Foo",
+                    TextRange {
+                        start: Position {
+                            line: 4,
+                            col: 23,
+                            byte_offset: 92
+                        },
+                        end: Position {
+                            line: 4,
+                            col: 41,
+                            byte_offset: 110
+                        },
+                    }
+                ),
+                (
+                    "#/definitions/Bar",
+                    TextRange {
+                        start: Position {
+                            line: 5,
+                            col: 24,
+                            byte_offset: 137
+                        },
+                        end: Position {
+                            line: 5,
+                            col: 42,
+                            byte_offset: 155
+                        },
+                    }
+                ),
+            ]
+        );
+    }
+}
+
+mod accessors {
+    use spanned_json_parser::{parse, value::Number};
+    use std::collections::HashMap;
+
+    #[test]
+    fn as_types() {
+        let parsed = parse(r#"{"s": "a", "n": 1, "b": true, "a": [null], "o": {}}"#).unwrap();
+        let obj = parsed.value.as_object().unwrap();
+
+        assert_eq!(obj["s"].value.as_string(), Some("a"));
+        assert_eq!(obj["n"].value.as_number(), Some(&Number::PosInt(1)));
+        assert_eq!(obj["b"].value.as_bool(), Some(true));
+        assert_eq!(
+            obj["a"].value.as_array().unwrap()[0].value.as_null(),
+            Some(())
+        );
+        assert!(obj["o"].value.as_object().unwrap().is_empty());
+
+        assert_eq!(obj["n"].value.as_string(), None);
+        assert_eq!(obj["s"].value.as_number(), None);
+        assert_eq!(obj["s"].value.as_bool(), None);
+        assert_eq!(obj["s"].value.as_null(), None);
+        assert!(obj["o"].value.as_array().is_none());
+        assert!(obj["a"].value.as_object().is_none());
+    }
+
+    #[test]
+    fn into_types() {
+        let parsed = parse(r#"{"s": "a", "a": [null, "b"], "o": {"k": 1}}"#).unwrap();
+
+        let mut obj: HashMap<_, _> = parsed.into_object().unwrap().into_iter().collect();
+
+        assert_eq!(obj.remove("s").unwrap().into_string(), Some("a".into()));
+
+        let array = obj.remove("a").unwrap().into_array().unwrap();
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[1].value.clone().into_string(), Some("b".into()));
+        assert_eq!(array[0].value.clone().into_string(), None);
+
+        let nested = obj.remove("o").unwrap().value.into_object().unwrap();
+        assert_eq!(nested["k"].value.unwrap_number(), &Number::PosInt(1));
+
+        assert!(parse("1").unwrap().into_array().is_none());
+        assert!(parse("[]").unwrap().into_object().is_none());
+    }
+}
+
+mod pointer {
+    use spanned_json_parser::{parse, value::Number, Position};
+
+    #[test]
+    fn arrays_and_objects() {
+        let parsed = parse(r#"{"vec": [{"num1": 1}, {"num2": 2}], "a/b": {"m~n": true}}"#).unwrap();
+
+        let num1 = parsed.pointer("/vec/0/num1").unwrap();
+        assert_eq!(num1.value.unwrap_number(), &Number::PosInt(1));
+        assert_eq!(
+            num1.start,
+            Position {
+                line: 1,
+                col: 19,
+                byte_offset: 18
+            }
+        );
+
+        assert!(parsed
+            .pointer("/vec/1")
+            .unwrap()
+            .value
+            .as_object()
+            .is_some());
+        assert!(parsed.pointer("/a~1b/m~0n").unwrap().value.unwrap_bool());
+        assert_eq!(parsed.pointer(""), Some(&parsed));
+    }
+
+    #[test]
+    fn missing_paths() {
+        let parsed = parse(r#"{"vec": [1, 2], "obj": {"key": null}}"#).unwrap();
+
+        assert!(parsed.pointer("/missing").is_none());
+        assert!(parsed.pointer("/vec/2").is_none());
+        assert!(parsed.pointer("/vec/01").is_none());
+        assert!(parsed.pointer("/vec/+1").is_none());
+        assert!(parsed.pointer("/vec/x").is_none());
+        assert!(parsed.pointer("/obj/key/deeper").is_none());
+        assert!(parsed.pointer("vec").is_none());
+    }
+}
+
+mod iter_descendants {
+    use spanned_json_parser::{parse, ValueType};
+
+    #[test]
+    fn document_order() {
+        let parsed = parse(r#"{"z": [1, {"y": 2}], "a": "s", "m": {}}"#).unwrap();
+
+        let types: Vec<ValueType> = parsed
+            .iter_descendants()
+            .map(|v| v.value.value_type())
+            .collect();
+
+        assert_eq!(
+            types,
+            vec![
+                ValueType::Array,
+                ValueType::Number,
+                ValueType::Object,
+                ValueType::Number,
+                ValueType::String,
+                ValueType::Object
+            ]
+        );
+
+        let cols: Vec<usize> = parsed.iter_descendants().map(|v| v.start.col).collect();
+
+        assert_eq!(cols, vec![7, 8, 11, 17, 27, 37]);
+    }
+
+    #[test]
+    fn scalar_has_no_descendants() {
+        assert_eq!(parse("1").unwrap().iter_descendants().count(), 0);
+    }
+}
+
+mod position_ord {
+    use spanned_json_parser::{parse, Position};
+
+    #[test]
+    fn line_then_col() {
+        let first = Position {
+            line: 1,
+            col: 5,
+            byte_offset: 4,
+        };
+        let second = Position {
+            line: 2,
+            col: 1,
+            byte_offset: 10,
+        };
+
+        assert!(first < second);
+        assert_eq!(first.clone().max(second.clone()), second);
+    }
+
+    #[test]
+    fn sort_spans() {
+        let parsed = parse("[\n  3,\n 2, 1]").unwrap();
+
+        let mut starts: Vec<Position> =
+            parsed.iter_descendants().map(|v| v.start.clone()).collect();
+        starts.reverse();
+        starts.sort();
+
+        let cols: Vec<(usize, usize)> = starts.iter().map(|p| (p.line, p.col)).collect();
+
+        assert_eq!(cols, vec![(2, 3), (3, 2), (3, 5)]);
+    }
+}
+
+mod text_range_merge {
+    use spanned_json_parser::{parse, Position};
+
+    #[test]
+    fn siblings() {
+        let parsed = parse("[1,\n  \"two\", 3]").unwrap();
+        let array = parsed.value.unwrap_array();
+
+        let merged = array[1].span().merge(&array[0].span());
+
+        assert_eq!(
+            merged.start,
+            Position {
+                line: 1,
+                col: 2,
+                byte_offset: 1
+            }
+        );
+        assert_eq!(
+            merged.end,
+            Position {
+                line: 2,
+                col: 7,
+                byte_offset: 10
+            }
+        );
+
+        assert_eq!(array[0].span().merge(&parsed.span()), parsed.span());
+    }
+}
+
+mod index {
+    use spanned_json_parser::{parse, value::Number, Position};
+
+    #[test]
+    fn chained() {
+        let parsed = parse(r#"{"vec": [{"num1": 1}, {"num2": 2}]}"#).unwrap();
+
+        let num1 = &parsed["vec"][0]["num1"];
+
+        assert_eq!(num1.value.unwrap_number(), &Number::PosInt(1));
+        assert_eq!(
+            num1.start,
+            Position {
+                line: 1,
+                col: 19,
+                byte_offset: 18
+            }
+        );
+        assert_eq!(
+            parsed
+                .get("vec")
+                .and_then(|v| v.get_index(1))
+                .and_then(|v| v.get("num2")),
+            Some(&parsed["vec"][1]["num2"])
+        );
+    }
+
+    #[test]
+    fn missing() {
+        let parsed = parse(r#"{"vec": [1]}"#).unwrap();
+
+        assert!(parsed.get("missing").is_none());
+        assert!(parsed.get_index(0).is_none());
+        assert!(parsed["vec"].get_index(1).is_none());
+        assert!(parsed["vec"].get("vec").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "no key `missing` in Object")]
+    fn missing_key_panics() {
+        let parsed = parse(r#"{"vec": [1]}"#).unwrap();
+
+        let _ = &parsed["missing"];
+    }
+
+    #[test]
+    #[should_panic(expected = "no index 1 in Array")]
+    fn missing_index_panics() {
+        let parsed = parse(r#"[1]"#).unwrap();
+
+        let _ = &parsed[1];
+    }
+}
+
+mod number_accessors {
+    use spanned_json_parser::{parse, parse_with_options, value::Number, ParseOptions};
+
+    #[test]
+    fn conversions() {
+        let parsed = parse("[1, -1, 1.5, 18446744073709551615, 1e2]").unwrap();
+        let numbers: Vec<&Number> = parsed
+            .value
+            .unwrap_array()
+            .iter()
+            .map(|v| v.value.unwrap_number())
+            .collect();
+
+        assert_eq!(numbers[0].as_u64(), Some(1));
+        assert_eq!(numbers[0].as_i64(), Some(1));
+        assert_eq!(numbers[0].as_f64(), 1.);
+        assert!(numbers[0].is_integer());
+
+        assert_eq!(numbers[1].as_u64(), None);
+        assert_eq!(numbers[1].as_i64(), Some(-1));
+        assert_eq!(numbers[1].as_f64(), -1.);
+        assert!(numbers[1].is_integer());
+
+        assert_eq!(numbers[2].as_u64(), None);
+        assert_eq!(numbers[2].as_i64(), None);
+        assert_eq!(numbers[2].as_f64(), 1.5);
+        assert!(!numbers[2].is_integer());
+
+        assert_eq!(numbers[3].as_u64(), Some(u64::MAX));
+        assert_eq!(numbers[3].as_i64(), None);
+        assert_eq!(numbers[3].as_f64(), u64::MAX as f64);
+
+        assert_eq!(numbers[4].as_i64(), None);
+        assert!(!numbers[4].is_integer());
+    }
+
+    #[test]
+    fn raw() {
+        let options = ParseOptions {
+            arbitrary_precision: true,
+            ..Default::default()
+        };
+        let parsed = parse_with_options(
+            "[12345678901234567890123, 0.12345678901234567890123]",
+            &options,
+        )
+        .unwrap();
+        let array = parsed.value.unwrap_array();
+
+        let int = array[0].value.unwrap_number();
+        assert!(int.is_integer());
+        assert_eq!(int.as_u64(), None);
+        assert_eq!(int.as_f64(), 12345678901234567890123.);
+
+        assert!(!array[1].value.unwrap_number().is_integer());
+    }
+}
+
+mod value_at {
+    use spanned_json_parser::{parse, Position};
+
+    fn cursor(line: usize, col: usize) -> Position {
+        Position {
+            line,
+            col,
+            byte_offset: 0,
+        }
+    }
+
+    #[test]
+    fn nested_element() {
+        let parsed = parse("{\n  \"vec\": [1, [22, 3]]\n}").unwrap();
+
+        let found = parsed.value_at(&cursor(2, 15)).unwrap();
+
+        assert_eq!(found, &parsed["vec"][1][0]);
+    }
+
+    #[test]
+    fn between_elements() {
+        let parsed = parse("{\n  \"vec\": [1, [22, 3]]\n}").unwrap();
+
+        assert_eq!(parsed.value_at(&cursor(2, 17)), Some(&parsed["vec"][1]));
+        assert_eq!(parsed.value_at(&cursor(2, 3)), Some(&parsed));
+    }
+
+    #[test]
+    fn outside() {
+        let parsed = parse("  [1]").unwrap();
+
+        assert_eq!(parsed.value_at(&cursor(1, 1)), None);
+        assert_eq!(parsed.value_at(&cursor(2, 1)), None);
+    }
+}
+
+mod source {
+    use spanned_json_parser::parse;
+
+    #[test]
+    fn original_text() {
+        let json = "{\n  \"big\": 1.2e12,\n  \"str\": \"\\u00e9t\u{e9}\",\n  \"vec\": [1, 2]\n}";
+        let parsed = parse(json).unwrap();
+
+        assert_eq!(parsed["big"].source(json), "1.2e12");
+        assert_eq!(parsed["str"].source(json), "\"\\u00e9t\u{e9}\"");
+        assert_eq!(parsed["vec"].source(json), "[1, 2]");
+        assert_eq!(parsed.source(json), json);
+    }
+
+    #[test]
+    fn other_source() {
+        let parsed = parse("  [1, 2]").unwrap();
+
+        assert_eq!(parsed.source("[]"), "");
+    }
+}
+
+mod eq_ignore_spans {
+    use spanned_json_parser::parse;
+
+    #[test]
+    fn formatting_and_key_order() {
+        let a = parse(r#"{"a": [1, {"b": null}], "c": "d"}"#).unwrap();
+        let b = parse("{\n  \"c\": \"d\",\n  \"a\": [\n    1,\n    {\"b\": null}\n  ]\n}").unwrap();
+
+        assert_ne!(a, b);
+        assert!(a.eq_ignore_spans(&b));
+        assert!(b.eq_ignore_spans(&a));
+    }
+
+    #[test]
+    fn different_values() {
+        let a = parse(r#"{"a": [1, 2]}"#).unwrap();
+
+        for other in [
+            r#"{"a": [2, 1]}"#,
+            r#"{"a": [1, 2], "b": 3}"#,
+            r#"{"b": [1, 2]}"#,
+            "[1, 2]",
+        ] {
+            assert!(!a.eq_ignore_spans(&parse(other).unwrap()), "{}", other);
+        }
+    }
+}
+
+mod hash {
+    use spanned_json_parser::{parse, value::Number, Value};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn spans_excluded() {
+        let parsed = parse("[{\"a\":1},\n   {\"a\": 1}, {\"a\": 2}]").unwrap();
+        let array = parsed.value.unwrap_array();
+
+        assert_ne!(array[0].start, array[1].start);
+        assert_eq!(hash(&array[0].value), hash(&array[1].value));
+        assert_ne!(hash(&array[0].value), hash(&array[2].value));
+    }
+
+    #[test]
+    fn key_order() {
+        let a = parse(r#"{"a": 1, "b": [true, null], "c": "d"}"#).unwrap();
+        let b = parse(r#"{"c": "d", "b": [true, null], "a": 1}"#).unwrap();
+
+        assert_eq!(hash(&a.value), hash(&b.value));
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(hash(&Number::Float(0.)), hash(&Number::Float(-0.)));
+        assert_ne!(hash(&Number::Float(1.)), hash(&Number::PosInt(1)));
+        assert_ne!(
+            hash(&Value::Number(Number::PosInt(1))),
+            hash(&Value::String("1".into()))
+        );
+    }
+}
+
+mod visit_mut {
+    use spanned_json_parser::{parse, Value};
+
+    #[test]
+    fn normalize() {
+        let mut parsed = parse(r#"{"Name": " app ", "Deps": [{"Id": "A "}, 1]}"#).unwrap();
+        let span = parsed["Deps"][0]["Id"].span();
+
+        parsed.visit_mut(|v| match &mut v.value {
+            Value::String(s) => *s = s.trim().to_string(),
+            Value::Object(obj) => {
+                *obj = std::mem::take(obj)
+                    .into_iter()
+                    .map(|(k, v)| (k.to_lowercase(), v))
+                    .collect();
+            }
+            _ => (),
+        });
+
+        assert_eq!(parsed["name"].value.unwrap_string(), "app");
+        assert_eq!(parsed["deps"][0]["id"].value.unwrap_string(), "A");
+        assert_eq!(parsed["deps"][0]["id"].span(), span);
+    }
+
+    #[test]
+    fn parents_first() {
+        let mut parsed = parse("[[1, [2]], 3]").unwrap();
+        let mut visited = Vec::new();
+
+        parsed.visit_mut(|v| visited.push(v.start.col));
+
+        assert_eq!(visited, vec![1, 2, 3, 6, 7, 12]);
+    }
+}
+
+mod from_primitives {
+    use spanned_json_parser::{parse, value::Number, Position, SpannedValue, Value};
+
+    #[test]
+    fn primitives() {
+        assert_eq!(Value::from("a"), Value::String("a".into()));
+        assert_eq!(Value::from(String::from("a")), Value::String("a".into()));
+        assert_eq!(Value::from(true), Value::Bool(true));
+        assert_eq!(Value::from(-1i64), Value::Number(Number::NegInt(-1)));
+        assert_eq!(Value::from(1i64), Value::Number(Number::PosInt(1)));
+        assert_eq!(Value::from(1u64), Value::Number(Number::PosInt(1)));
+        assert_eq!(Value::from(1.5), Value::Number(Number::Float(1.5)));
+        assert_eq!(Value::from(vec![]), Value::Array(vec![]));
+    }
+
+    #[test]
+    fn builders() {
+        let expected = Value::object([
+            ("name", Value::from("app")),
+            ("ids", Value::array([1i64, -2])),
+            ("nested", Value::object([("ok", true)])),
+        ]);
+        let parsed = parse(r#"{"ids": [1, -2], "nested": {"ok": true}, "name": "app"}"#).unwrap();
+
+        assert!(parsed.value.value_eq(&expected));
+
+        let element: SpannedValue = Value::from(1u64).into();
+
+        assert_eq!(element.start, Position::default());
+        assert_eq!(element.end, Position::default());
+        assert_eq!(element.key_span, None);
+    }
+}
+
+mod type_name {
+    use spanned_json_parser::{parse, ValueType};
+
+    #[test]
+    fn every_variant() {
+        let parsed = parse(r#"[null, 1, "a", true, [], {}]"#).unwrap();
+        let names: Vec<_> = parsed
+            .value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.value.type_name())
+            .collect();
+
+        assert_eq!(
+            names,
+            ["null", "number", "string", "bool", "array", "object"]
+        );
+    }
+
+    #[test]
+    fn same_as_value_type() {
+        let parsed = parse(r#"[null, 1, "a", true, [], {}]"#).unwrap();
+
+        for value in parsed.value.unwrap_array() {
+            assert_eq!(
+                value.value.type_name(),
+                value.value.value_type().type_name()
+            );
+        }
+
+        assert_eq!(ValueType::Object.type_name(), "object");
+    }
+}
+
+mod flatten {
+    use spanned_json_parser::parse;
+
+    #[test]
+    fn pointers() {
+        let data = r#"{"a": {"b/c": [1, {"~d": null}]}, "": true}"#;
+        let parsed = parse(data).unwrap();
+
+        let flat = parsed.flatten();
+        let pointers: Vec<_> = flat.iter().map(|(p, _)| p.as_str()).collect();
+
+        assert_eq!(
+            pointers,
+            [
+                "",
+                "/a",
+                "/a/b~1c",
+                "/a/b~1c/0",
+                "/a/b~1c/1",
+                "/a/b~1c/1/~0d",
+                "/"
+            ]
+        );
+
+        for (pointer, value) in &flat {
+            assert!(std::ptr::eq(parsed.pointer(pointer).unwrap(), *value));
+        }
+
+        assert_eq!(flat[3].1.start.col, 16);
+    }
+
+    #[test]
+    fn scalar_root() {
+        let parsed = parse("1").unwrap();
+
+        assert_eq!(parsed.flatten(), vec![(String::new(), &parsed)]);
+    }
+}